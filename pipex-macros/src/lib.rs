@@ -301,6 +301,162 @@ pub fn error_strategy(args: TokenStream, item: TokenStream) -> TokenStream {
     TokenStream::from(expanded)
 }
 
+/// Circuit breaker configuration for the `#[circuit_breaker]` attribute
+struct CircuitBreakerArgs {
+    failures: u32,
+    reset: String,
+}
+
+impl Parse for CircuitBreakerArgs {
+    fn parse(input: ParseStream) -> SynResult<Self> {
+        let mut args = CircuitBreakerArgs::default();
+        
+        // Parse optional arguments like: failures = 5, reset = "30s"
+        while !input.is_empty() {
+            let ident: Ident = input.parse()?;
+            input.parse::<syn::Token![=]>()?;
+            let lit: Lit = input.parse()?;
+            match (ident.to_string().as_str(), &lit) {
+                ("failures", Lit::Int(lit_int)) => {
+                    args.failures = lit_int.base10_parse()?;
+                    if args.failures == 0 {
+                        return Err(Error::new_spanned(lit, "failures must be at least 1"));
+                    }
+                }
+                ("failures", _) => return Err(Error::new_spanned(lit, "failures must be an integer")),
+                ("reset", Lit::Str(lit_str)) => args.reset = lit_str.value(),
+                ("reset", _) => return Err(Error::new_spanned(lit, "reset must be a string such as \"30s\"")),
+                _ => return Err(Error::new_spanned(ident, "unknown attribute argument")),
+            }
+            
+            // Handle optional comma
+            if input.peek(syn::Token![,]) {
+                input.parse::<syn::Token![,]>()?;
+            }
+        }
+        
+        Ok(args)
+    }
+}
+
+impl Default for CircuitBreakerArgs {
+    fn default() -> Self {
+        Self { failures: 5, reset: "30s".to_string() } // 5 failures, 30 seconds
+    }
+}
+
+/// The `circuit_breaker` attribute macro for async functions returning `Result<T, E>`
+/// 
+/// After `failures` consecutive errors the function stops running and fails fast
+/// with a `CircuitOpenError` until `reset` has passed; the next call is then let
+/// through as a probe, closing the breaker again if it succeeds. The breaker state
+/// lives in a static, so it is shared by every pipeline run calling the function.
+/// 
+/// # Arguments
+/// - `failures` (optional): Consecutive failures that open the breaker (default: 5)
+/// - `reset` (optional): Cooldown before probing again, such as `"30s"` (default: `"30s"`),
+///   parsed by `pipex::time::parse_duration` when the breaker is created
+/// 
+/// # Requirements
+/// - The error type must implement `From<CircuitOpenError>` (`String` does)
+/// - Requires the "async" feature to be enabled
+/// - Combined with `#[error_strategy]`, `#[circuit_breaker]` goes first so that
+///   breaker-open errors go through the strategy too
+/// 
+/// # Examples
+/// 
+/// ```rust,ignore
+/// use pipex_macros::{circuit_breaker, error_strategy};
+/// 
+/// #[circuit_breaker(failures = 5, reset = "30s")]
+/// #[error_strategy(LogAndIgnoreHandler)]
+/// async fn fetch_profile(id: u64) -> Result<String, String> {
+///     call_profile_service(id).await
+/// }
+/// ```
+#[proc_macro_attribute]
+pub fn circuit_breaker(args: TokenStream, item: TokenStream) -> TokenStream {
+    let args = parse_macro_input!(args as CircuitBreakerArgs);
+    let input_fn = parse_macro_input!(item as ItemFn);
+    
+    if input_fn.sig.asyncness.is_none() {
+        return Error::new_spanned(
+            &input_fn.sig,
+            "circuit_breaker requires an async function"
+        ).to_compile_error().into();
+    }
+    
+    let fn_attrs = &input_fn.attrs;
+    let fn_name = &input_fn.sig.ident;
+    let fn_vis = &input_fn.vis;
+    let fn_inputs = &input_fn.sig.inputs;
+    let fn_output = &input_fn.sig.output;
+    let fn_body = &input_fn.block;
+    let fn_generics = &input_fn.sig.generics;
+    let where_clause = &input_fn.sig.generics.where_clause;
+    
+    // Validate the return type is Result<T, E>
+    if let Err(e) = match fn_output {
+        ReturnType::Type(_, ty) => extract_result_types(ty).map(|_| ()),
+        ReturnType::Default => Err(Error::new_spanned(&input_fn.sig, "Function must return Result<T, E>")),
+    } {
+        return e.to_compile_error().into();
+    }
+    
+    let breaker_name = Ident::new(&format!("{}_CIRCUIT_BREAKER", fn_name.to_string().to_uppercase()), fn_name.span());
+    let original_fn_name = Ident::new(&format!("{}_unguarded", fn_name), fn_name.span());
+    let fn_name_str = fn_name.to_string();
+    let failures = args.failures;
+    let reset = &args.reset;
+    
+    // The guarded function binds every parameter to a plain name to forward it, so patterns
+    // such as `(a, b): (i32, i32)` or `_` are only destructured by the unguarded function
+    let mut guarded_inputs = fn_inputs.clone();
+    let mut param_names = Vec::new();
+    for (i, arg) in guarded_inputs.iter_mut().enumerate() {
+        let pat_type = match arg {
+            syn::FnArg::Typed(pat_type) => pat_type,
+            syn::FnArg::Receiver(receiver) => {
+                return Error::new_spanned(receiver, "circuit_breaker does not support methods")
+                    .to_compile_error().into();
+            }
+        };
+        let name = match &*pat_type.pat {
+            syn::Pat::Ident(pat_ident) if pat_ident.by_ref.is_none() && pat_ident.subpat.is_none() => {
+                pat_ident.ident.clone()
+            }
+            _ => Ident::new(&format!("__arg{}", i), pat_type.pat.span()),
+        };
+        *pat_type.pat = syn::parse_quote!(#name);
+        param_names.push(name);
+    }
+    
+    let expanded = quote! {
+        #[doc(hidden)]
+        async fn #original_fn_name #fn_generics (#fn_inputs) #fn_output #where_clause
+        #fn_body
+        
+        // Other attributes such as #[error_strategy] apply to the guarded function
+        #(#fn_attrs)*
+        #fn_vis async fn #fn_name #fn_generics (#guarded_inputs) #fn_output #where_clause {
+            // Shared by every call of this function
+            static #breaker_name: std::sync::OnceLock<crate::circuit::CircuitBreaker> = std::sync::OnceLock::new();
+            
+            let breaker = #breaker_name.get_or_init(|| {
+                let reset = match crate::time::parse_duration(#reset) {
+                    Some(reset) => reset,
+                    None => panic!("invalid circuit breaker reset `{}`, expected e.g. \"500ms\", \"30s\" or \"2m\"", #reset),
+                };
+                crate::circuit::CircuitBreaker::new(#fn_name_str, #failures, reset)
+            });
+            
+            breaker.call(#original_fn_name(#(#param_names),*)).await
+        }
+    };
+    
+    TokenStream::from(expanded)
+}
+
 /// Memoization configuration for the `#[memoized]` attribute
 struct MemoizedArgs {
     capacity: Option<usize>,
@@ -402,6 +558,11 @@ pub fn memoized(args: TokenStream, item: TokenStream) -> TokenStream {
     let where_clause = &input_fn.sig.generics.where_clause;
     let fn_asyncness = &input_fn.sig.asyncness;
     
+    // Lint attributes such as #[allow(...)] apply to the body, which moves to the original function
+    let lint_attrs: Vec<_> = input_fn.attrs.iter().filter(|attr| {
+        ["allow", "expect", "warn", "deny", "forbid"].iter().any(|lint| attr.path().is_ident(lint))
+    }).collect();
+    
     // Generate cache name
     let cache_name = Ident::new(&format!("{}_CACHE", fn_name.to_string().to_uppercase()), fn_name.span());
     
@@ -470,10 +631,12 @@ pub fn memoized(args: TokenStream, item: TokenStream) -> TokenStream {
     
     let expanded = quote! {
         // Original function implementation
+        #(#lint_attrs)*
         #fn_asyncness fn #original_fn_name #fn_generics (#fn_inputs) #fn_output #where_clause
         #fn_body
         
         // Memoized wrapper function
+        #(#lint_attrs)*
         #fn_vis #fn_asyncness fn #fn_name #fn_generics (#fn_inputs) #fn_output #where_clause {
             #[cfg(feature = "memoization")]
             {
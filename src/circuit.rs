@@ -0,0 +1,189 @@
+//! Circuit breakers for asynchronous step functions
+//!
+//! These back the [`circuit_breaker`](crate::circuit_breaker) attribute and are also usable
+//! on their own. Requires the "async" feature; time is measured on tokio's clock.
+
+use std::fmt;
+use std::future::Future;
+use std::sync::Mutex;
+use std::time::Duration;
+
+use tokio::time::Instant;
+
+/// Error returned instead of calling a dependency while its circuit breaker is open
+///
+/// Pipelines whose error type is `String` receive it through its `Display` form,
+/// `"circuit breaker for `{name}` is open"`. Pipelines with their own error type
+/// implement `From<CircuitOpenError>` for it.
+///
+/// # Examples
+///
+/// ```rust
+/// use pipex::circuit::CircuitOpenError;
+///
+/// let error = CircuitOpenError { name: "fetch_user" };
+/// assert_eq!(String::from(error), "circuit breaker for `fetch_user` is open");
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct CircuitOpenError {
+    /// Name of the protected function
+    pub name: &'static str,
+}
+
+impl fmt::Display for CircuitOpenError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "circuit breaker for `{}` is open", self.name)
+    }
+}
+
+impl std::error::Error for CircuitOpenError {}
+
+impl From<CircuitOpenError> for String {
+    fn from(error: CircuitOpenError) -> Self {
+        error.to_string()
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum State {
+    Closed { failures: u32 },
+    Open { until: Instant },
+    // A single probe call is in flight, everything else fails fast
+    HalfOpen,
+}
+
+#[derive(Debug)]
+struct Breaker {
+    state: State,
+    // Bumped whenever the breaker opens, probes or closes after a probe, so outcomes of calls
+    // admitted before that are ignored
+    generation: u64,
+}
+
+impl Breaker {
+    fn enter(&mut self, state: State) {
+        self.state = state;
+        self.generation += 1;
+    }
+}
+
+/// Circuit breaker guarding calls to one dependency
+///
+/// While closed, calls go through and consecutive failures are counted. After
+/// `failure_threshold` failures in a row the breaker opens and calls fail fast with a
+/// [`CircuitOpenError`]. Once `reset` has passed, the next call is let through as a probe:
+/// if it succeeds the breaker closes again, otherwise it stays open for another `reset`.
+///
+/// Only calls admitted in the current state count: a slow call that started before the breaker
+/// opened cannot close it by succeeding late. Calls dropped before finishing, for example by a
+/// timeout or a `fail_fast` pipeline, are not counted as failures; a dropped probe lets the next
+/// call probe instead.
+///
+/// # Examples
+///
+/// ```rust
+/// use pipex::circuit::{CircuitBreaker, CircuitOpenError};
+/// use std::time::Duration;
+///
+/// # tokio_test::block_on(async {
+/// let breaker = CircuitBreaker::new("flaky", 1, Duration::from_secs(30));
+///
+/// let first: Result<(), String> = breaker.call(async { Err("down".to_string()) }).await;
+/// assert_eq!(first, Err("down".to_string()));
+///
+/// let second: Result<(), String> = breaker.call(async { Ok(()) }).await;
+/// assert_eq!(second, Err(CircuitOpenError { name: "flaky" }.into()));
+/// # });
+/// ```
+#[derive(Debug)]
+pub struct CircuitBreaker {
+    name: &'static str,
+    failure_threshold: u32,
+    reset: Duration,
+    state: Mutex<Breaker>,
+}
+
+impl CircuitBreaker {
+    /// Create a closed breaker that opens after `failure_threshold` failures in a row
+    ///
+    /// # Panics
+    ///
+    /// Panics if `failure_threshold` is zero.
+    pub fn new(name: &'static str, failure_threshold: u32, reset: Duration) -> Self {
+        assert!(failure_threshold > 0, "a circuit breaker requires a failure threshold of at least 1");
+        Self {
+            name,
+            failure_threshold,
+            reset,
+            state: Mutex::new(Breaker { state: State::Closed { failures: 0 }, generation: 0 }),
+        }
+    }
+
+    /// Whether calls currently fail fast
+    pub fn is_open(&self) -> bool {
+        match self.state.lock().unwrap().state {
+            State::Closed { .. } => false,
+            State::Open { until } => Instant::now() < until,
+            State::HalfOpen => true,
+        }
+    }
+
+    /// Run `call` if the breaker lets it through and record its outcome
+    pub async fn call<T, E, F>(&self, call: F) -> Result<T, E>
+    where
+        F: Future<Output = Result<T, E>>,
+        E: From<CircuitOpenError>,
+    {
+        let generation = self.acquire().map_err(E::from)?;
+
+        // Records nothing but a dropped probe if the call does not finish
+        let mut outcome = Outcome { breaker: self, generation, success: None };
+        let result = call.await;
+        outcome.success = Some(result.is_ok());
+        result
+    }
+
+    // Let a call through, returning the generation it was admitted in
+    fn acquire(&self) -> Result<u64, CircuitOpenError> {
+        let mut breaker = self.state.lock().unwrap();
+        match breaker.state {
+            State::Closed { .. } => Ok(breaker.generation),
+            State::Open { until } if Instant::now() >= until => {
+                breaker.enter(State::HalfOpen);
+                Ok(breaker.generation)
+            }
+            State::Open { .. } | State::HalfOpen => Err(CircuitOpenError { name: self.name }),
+        }
+    }
+
+    // Record the outcome of a call admitted in `generation`, `None` if it was dropped
+    fn record(&self, generation: u64, success: Option<bool>) {
+        let mut breaker = self.state.lock().unwrap();
+        if breaker.generation != generation {
+            return;
+        }
+        match (breaker.state, success) {
+            (State::Closed { .. }, Some(true)) => breaker.state = State::Closed { failures: 0 },
+            (State::Closed { failures }, Some(false)) if failures + 1 < self.failure_threshold => {
+                breaker.state = State::Closed { failures: failures + 1 };
+            }
+            (State::Closed { .. }, None) => {}
+            (_, Some(true)) => breaker.enter(State::Closed { failures: 0 }),
+            (_, Some(false)) => breaker.enter(State::Open { until: Instant::now() + self.reset }),
+            // The probe was dropped, so the next call probes again
+            (_, None) => breaker.enter(State::Open { until: Instant::now() }),
+        }
+    }
+}
+
+struct Outcome<'a> {
+    breaker: &'a CircuitBreaker,
+    generation: u64,
+    success: Option<bool>,
+}
+
+impl Drop for Outcome<'_> {
+    fn drop(&mut self) {
+        self.breaker.record(self.generation, self.success);
+    }
+}
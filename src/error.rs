@@ -0,0 +1,226 @@
+//! Errors produced by the pipeline itself
+//!
+//! Steps pass the errors returned by user closures through unchanged. Errors that a step
+//! creates on its own reach the pipeline's error type `E` through `From`; every error type
+//! here converts into `String` via its `Display` form.
+//!
+//! A pipeline started with `pipex!(traced input => ...)` wraps each error in a
+//! [`PipelineError`] recording the step that failed and the input item it came from.
+
+use std::fmt;
+
+/// Error for items of an `async batch` step whose batch call did not return a result for them
+///
+/// # Examples
+///
+/// ```rust
+/// use pipex::error::BatchError;
+///
+/// let error = BatchError::WrongLength { results: 2, inputs: 3 };
+/// assert_eq!(String::from(error), "batch call returned 2 results for 3 inputs");
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum BatchError {
+    /// The returned `Vec` had a different length than the batch
+    WrongLength {
+        /// Number of results returned
+        results: usize,
+        /// Number of items in the batch
+        inputs: usize,
+    },
+    /// The returned map had no entry for the item, shown with its `Debug` form
+    MissingResult(String),
+}
+
+impl fmt::Display for BatchError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            BatchError::WrongLength { results, inputs } => {
+                write!(f, "batch call returned {} results for {} inputs", results, inputs)
+            }
+            BatchError::MissingResult(key) => write!(f, "batch call returned no result for {}", key),
+        }
+    }
+}
+
+impl std::error::Error for BatchError {}
+
+impl From<BatchError> for String {
+    fn from(error: BatchError) -> Self {
+        error.to_string()
+    }
+}
+
+/// Error for an item whose closure panicked in a `catch` step
+///
+/// The message is the panic's payload when it is a string, as it is for `panic!` with a
+/// message, `unwrap` and `expect`. Pipelines whose error type is `String` receive the
+/// `Display` form, `"panicked: {message}"`.
+///
+/// # Examples
+///
+/// ```rust
+/// use pipex::error::PanicError;
+///
+/// let error = PanicError::new("index out of bounds");
+/// assert_eq!(String::from(error), "panicked: index out of bounds");
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct PanicError {
+    /// The panic message
+    pub message: String,
+}
+
+impl PanicError {
+    /// Create a panic error with the given message
+    pub fn new(message: impl Into<String>) -> Self {
+        Self { message: message.into() }
+    }
+
+    /// Create a panic error from the payload returned by `catch_unwind`
+    pub fn from_payload(payload: Box<dyn std::any::Any + Send>) -> Self {
+        let message = match payload.downcast::<String>() {
+            Ok(message) => *message,
+            Err(payload) => match payload.downcast::<&'static str>() {
+                Ok(message) => message.to_string(),
+                Err(_) => "panic payload is not a string".to_string(),
+            },
+        };
+        Self { message }
+    }
+}
+
+impl fmt::Display for PanicError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "panicked: {}", self.message)
+    }
+}
+
+impl std::error::Error for PanicError {}
+
+impl From<PanicError> for String {
+    fn from(error: PanicError) -> Self {
+        error.to_string()
+    }
+}
+
+/// Error for an item whose task did not finish in a `spawn async` or `blocking` step
+///
+/// # Examples
+///
+/// ```rust
+/// use pipex::error::TaskError;
+///
+/// let error = TaskError::Panicked("index out of bounds".to_string());
+/// assert_eq!(String::from(error), "task panicked: index out of bounds");
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum TaskError {
+    /// The task panicked, with the panic message as in [`PanicError`]
+    Panicked(String),
+    /// The task was cancelled, e.g. because the runtime shut down
+    Cancelled,
+}
+
+impl fmt::Display for TaskError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            TaskError::Panicked(message) => write!(f, "task panicked: {}", message),
+            TaskError::Cancelled => write!(f, "task was cancelled"),
+        }
+    }
+}
+
+impl std::error::Error for TaskError {}
+
+impl From<TaskError> for String {
+    fn from(error: TaskError) -> Self {
+        error.to_string()
+    }
+}
+
+#[cfg(feature = "async")]
+impl From<tokio::task::JoinError> for TaskError {
+    fn from(error: tokio::task::JoinError) -> Self {
+        match error.try_into_panic() {
+            Ok(payload) => TaskError::Panicked(PanicError::from_payload(payload).message),
+            Err(_) => TaskError::Cancelled,
+        }
+    }
+}
+
+/// Error of a `traced` pipeline: the cause together with where it happened
+///
+/// `pipex!(traced input => ...)` wraps every error a step produces, including errors the
+/// step creates itself such as timeouts, in a `PipelineError`. Errors keep the position
+/// of the step that produced them as they pass through later steps. Label a step with
+/// `"name": step` to record a name alongside its position.
+///
+/// # Examples
+///
+/// ```rust
+/// use pipex::pipex;
+/// use pipex::error::PipelineError;
+///
+/// let result = pipex!(
+///     traced vec!["1", "x", "3"]
+///     => "parse": |s| s.parse::<i32>().map_err(|_| format!("not a number: {}", s))
+///     => |n| Ok::<_, String>(n * 2)
+/// );
+///
+/// assert_eq!(result[0], Ok(2));
+/// assert_eq!(result[1], Err(PipelineError {
+///     stage: 0,
+///     stage_name: Some("parse"),
+///     input_index: 1,
+///     cause: "not a number: x".to_string(),
+/// }));
+/// assert_eq!(
+///     result[1].as_ref().unwrap_err().to_string(),
+///     "stage 0 (`parse`) failed for input 1: not a number: x"
+/// );
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct PipelineError<E> {
+    /// Position of the failing step in the pipeline, starting at 0
+    pub stage: usize,
+    /// Name of the failing step, if it was labelled
+    pub stage_name: Option<&'static str>,
+    /// Position of the item in the pipeline's input, starting at 0
+    pub input_index: usize,
+    /// The error the step produced
+    pub cause: E,
+}
+
+impl<E> PipelineError<E> {
+    /// Replace the cause, keeping the record of where it happened
+    pub fn map_cause<C, F>(self, f: F) -> PipelineError<C>
+    where
+        F: FnOnce(E) -> C,
+    {
+        PipelineError {
+            stage: self.stage,
+            stage_name: self.stage_name,
+            input_index: self.input_index,
+            cause: f(self.cause),
+        }
+    }
+}
+
+impl<E: fmt::Display> fmt::Display for PipelineError<E> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "stage {}", self.stage)?;
+        if let Some(name) = self.stage_name {
+            write!(f, " (`{}`)", name)?;
+        }
+        write!(f, " failed for input {}: {}", self.input_index, self.cause)
+    }
+}
+
+impl<E: fmt::Debug + fmt::Display> std::error::Error for PipelineError<E> {}
+
+impl<E: fmt::Display> From<PipelineError<E>> for String {
+    fn from(error: PipelineError<E>) -> Self {
+        error.to_string()
+    }
+}
@@ -16,7 +16,7 @@ use std::borrow::Cow;
 use wgpu::util::DeviceExt;
 
 /// GPU computation errors
-#[derive(Debug, thiserror::Error)]
+#[derive(Debug, Clone, thiserror::Error)]
 pub enum GpuError {
     /// GPU device or adapter initialization failed
     #[error("GPU device initialization failed: {0}")]
@@ -39,6 +39,12 @@ pub enum GpuError {
     DataTransferFailed(String),
 }
 
+impl From<GpuError> for String {
+    fn from(error: GpuError) -> Self {
+        format!("GPU execution failed: {}", error)
+    }
+}
+
 /// GPU pipeline for executing compute shaders
 pub struct GpuPipeline {
     device: wgpu::Device,
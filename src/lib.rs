@@ -9,18 +9,28 @@
 // Core modules
 mod result;
 pub mod traits;
+pub mod error;
+pub mod time;
+pub mod retry;
+#[doc(hidden)]
+pub mod short_circuit;
+#[cfg(feature = "async")]
+#[doc(hidden)]
+pub mod task;
 mod handlers;
 mod macros;
 
 // Re-export public API
 pub use result::PipexResult;
-pub use traits::{PipelineResultHandler, ExtractSuccessful, IntoResult, CreateError};
+pub use traits::{
+    PipelineResultHandler, ExtractSuccessful, ExtractErrors, PartitionResults, IntoResult, CreateError
+};
 pub use handlers::{
     ErrorHandler, IgnoreHandler, CollectHandler, FailFastHandler, LogAndIgnoreHandler
 };
 
 // Re-export the proc macros
-pub use pipex_macros::{error_strategy, pure, memoized};
+pub use pipex_macros::{error_strategy, pure, memoized, circuit_breaker};
 
 // Conditional re-exports based on features
 #[cfg(feature = "async")]
@@ -43,6 +53,18 @@ pub use dashmap;
 #[cfg_attr(docsrs, doc(cfg(feature = "memoization")))]
 pub use once_cell;
 
+#[cfg(feature = "async")]
+#[cfg_attr(docsrs, doc(cfg(feature = "async")))]
+pub mod stream;
+
+#[cfg(feature = "async")]
+#[cfg_attr(docsrs, doc(cfg(feature = "async")))]
+pub mod rate;
+
+#[cfg(feature = "async")]
+#[cfg_attr(docsrs, doc(cfg(feature = "async")))]
+pub mod circuit;
+
 #[cfg(feature = "gpu")]
 #[cfg_attr(docsrs, doc(cfg(feature = "gpu")))]
 pub mod gpu;
@@ -59,13 +81,16 @@ use std::collections::HashMap;
 use std::sync::{Mutex, OnceLock};
 use std::any::{Any, TypeId};
 
+/// Signature shared by all strategy handlers
+type StrategyFn<T, E> = fn(Vec<Result<T, E>>) -> Vec<Result<T, E>>;
+
 // Registry for strategy functions
 static STRATEGY_REGISTRY: OnceLock<Mutex<HashMap<String, Box<dyn Any + Send + Sync>>>> = OnceLock::new();
 
 /// Register a custom strategy handler for specific types
 pub fn register_strategy<T, E>(
     name: &str,
-    handler: fn(Vec<Result<T, E>>) -> Vec<Result<T, E>>
+    handler: StrategyFn<T, E>
 ) where
     T: 'static,
     E: std::fmt::Debug + 'static,
@@ -79,11 +104,11 @@ pub fn register_strategy<T, E>(
     registry.insert(key, Box::new(handler));
 }
 
-/// Try to call a registered strategy (fix ownership issue by borrowing)
-fn try_registered_strategy<T, E>(strategy_name: &str, results: &Vec<Result<T, E>>) -> Option<Vec<Result<T, E>>>
+/// Look up a registered strategy handler for these types
+fn registered_strategy<T, E>(strategy_name: &str) -> Option<StrategyFn<T, E>>
 where
-    T: 'static + Clone,
-    E: std::fmt::Debug + 'static + Clone,
+    T: 'static,
+    E: std::fmt::Debug + 'static,
 {
     let registry = STRATEGY_REGISTRY.get()?;
     let registry = registry.lock().unwrap();
@@ -91,32 +116,29 @@ where
     let type_id = (TypeId::of::<T>(), TypeId::of::<E>());
     let key = format!("{}_{:?}", strategy_name, type_id);
     
-    if let Some(handler_any) = registry.get(&key) {
-        if let Some(handler) = handler_any.downcast_ref::<fn(Vec<Result<T, E>>) -> Vec<Result<T, E>>>() {
-            return Some(handler(results.clone()));
-        }
-    }
-    
-    None
+    registry.get(&key)?.downcast_ref::<StrategyFn<T, E>>().copied()
 }
 
 /// Apply strategy - checks registry first, then built-ins
 pub fn apply_strategy<T, E>(strategy_name: &str, results: Vec<Result<T, E>>) -> Vec<Result<T, E>>
 where
-    T: 'static + Clone,
-    E: std::fmt::Debug + 'static + Clone,
+    T: 'static,
+    E: std::fmt::Debug + 'static,
 {
-    // Try registered strategies first (pass by reference to avoid move)
-    if let Some(result) = try_registered_strategy(strategy_name, &results) {
-        return result;
+    // Try registered strategies first
+    if let Some(handler) = registered_strategy::<T, E>(strategy_name) {
+        return handler(results);
     }
     
-    // Fall back to built-ins (now we can still use results since we didn't move it)
+    // Fall back to built-ins
     match strategy_name {
         "IgnoreHandler" => IgnoreHandler::handle_results(results),
         "CollectHandler" => CollectHandler::handle_results(results),
         "FailFastHandler" => FailFastHandler::handle_results(results),
         "LogAndIgnoreHandler" => LogAndIgnoreHandler::handle_results(results),
+        // Errors created by a step itself (timeouts, panics, ...) when no item of the stage
+        // brought a strategy along: they are kept as they are
+        "preserve_error" => results,
         _ => {
             eprintln!("Warning: Unknown strategy '{}'. Use register_strategy() to register custom handlers.", strategy_name);
             results
@@ -124,6 +146,9 @@ where
     }
 }
 
+#[doc(hidden)]
+pub use traits::IntoPipelineItem as __InternalIntoPipelineItem;
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -145,13 +170,14 @@ mod tests {
     fn setup() {
         INIT.call_once(|| {
             register_strategies!( FirstErrorHandler for <i32, String> );
+            register_strategies!( FirstErrorHandler for <(usize, i32), (usize, String)> );
         });
     }
 
     pub fn apply_strategy<T, E>(strategy_name: &str, results: Vec<Result<T, E>>) -> Vec<Result<T, E>>
     where
-        T: 'static + Clone,
-        E: std::fmt::Debug + 'static + Clone,
+        T: 'static,
+        E: std::fmt::Debug + 'static,
     {
         setup(); // Ensure registration
         crate::apply_strategy(strategy_name, results)
@@ -278,33 +304,757 @@ mod tests {
         assert!(result.iter().all(|r| r.is_ok()));
         
         let values: Vec<i32> = result.into_iter().map(|r| r.unwrap()).collect();
-        assert_eq!(values, vec![2, 4, 8, 10]); // 1*2, 2*2, 4*2, 5*2
+        assert_eq!(values, vec![2, 4, 8, 10]); // 1*2, 2*2, 4*2, 5*2
+    }
+
+    #[tokio::test]
+    async fn test_strategy_failfast() {
+        let result = pipex!(
+            vec![1, 2, 3, 4, 5, 3]
+            => async |x| { process_with_failfast(x).await }
+        );
+        
+        // Should fail fast - return only errors
+        assert!(result.iter().all(|r| r.is_err()));
+        assert_eq!(result.len(), 2); // Two instances of 3
+        eprintln!("result: {:?}", result);
+    }
+
+    #[tokio::test]
+    async fn test_custom_first_error_handler() {
+        let result = pipex!(
+            vec![1, 2, 3, 4, 5]
+            => async |x| { process_with_first_error(x).await }
+        );
+        
+        // Should return only the first error
+        assert_eq!(result.len(), 1);
+        assert!(result[0].is_err());
+        eprintln!("FirstErrorHandler result: {:?}", result);
+    }
+
+    #[tokio::test]
+    async fn test_bounded_async_pipeline() {
+        use std::sync::atomic::{AtomicUsize, Ordering};
+
+        let in_flight = &AtomicUsize::new(0);
+        let max_in_flight = &AtomicUsize::new(0);
+
+        let result = pipex!(
+            (1..=20).collect::<Vec<i32>>()
+            => async(4) |x| {
+                let current = in_flight.fetch_add(1, Ordering::SeqCst) + 1;
+                max_in_flight.fetch_max(current, Ordering::SeqCst);
+                tokio::task::yield_now().await;
+                in_flight.fetch_sub(1, Ordering::SeqCst);
+                Ok::<i32, String>(x * 2)
+            }
+        );
+
+        // Never more than 4 futures in flight, and output keeps input order
+        assert!(max_in_flight.load(Ordering::SeqCst) <= 4);
+        let values: Vec<i32> = result.into_iter().map(|r| r.unwrap()).collect();
+        assert_eq!(values, (1..=20).map(|x| x * 2).collect::<Vec<i32>>());
+    }
+
+    #[tokio::test]
+    async fn test_bounded_async_with_strategy() {
+        let result = pipex!(
+            vec![1, 2, 3, 4, 5]
+            => async(2) |x| { process_and_ignore(x).await }
+            => |x| Ok::<i32, String>(x + 1)
+        );
+
+        // IgnoreHandler still drops item 3, order is preserved
+        let values: Vec<i32> = result.into_iter().map(|r| r.unwrap()).collect();
+        assert_eq!(values, vec![3, 5, 9, 11]);
+    }
+
+    static SPAWNED_IN_FLIGHT: std::sync::atomic::AtomicUsize = std::sync::atomic::AtomicUsize::new(0);
+    static SPAWNED_MAX_IN_FLIGHT: std::sync::atomic::AtomicUsize = std::sync::atomic::AtomicUsize::new(0);
+    static SPAWNED_FINISHED: std::sync::atomic::AtomicUsize = std::sync::atomic::AtomicUsize::new(0);
+
+    #[tokio::test(flavor = "multi_thread", worker_threads = 4)]
+    async fn test_spawn_async_step() {
+        use std::sync::atomic::Ordering;
+
+        let result = pipex!(
+            (1..=20).collect::<Vec<i32>>()
+            => spawn(3) async |x| {
+                let current = SPAWNED_IN_FLIGHT.fetch_add(1, Ordering::SeqCst) + 1;
+                SPAWNED_MAX_IN_FLIGHT.fetch_max(current, Ordering::SeqCst);
+                tokio::task::yield_now().await;
+                SPAWNED_IN_FLIGHT.fetch_sub(1, Ordering::SeqCst);
+                if x == 7 {
+                    panic!("bad item {}", x);
+                }
+                Ok::<_, String>(x * 2)
+            }
+        );
+
+        // Never more than 3 tasks at once, a panicking task only fails its own item
+        assert!(SPAWNED_MAX_IN_FLIGHT.load(Ordering::SeqCst) <= 3);
+        let expected = (1..=20)
+            .map(|x| if x == 7 { Err("task panicked: bad item 7".to_string()) } else { Ok(x * 2) })
+            .collect::<Vec<_>>();
+        assert_eq!(result, expected);
+    }
+
+    #[tokio::test(start_paused = true)]
+    async fn test_fail_fast_aborts_spawned_tasks() {
+        use std::sync::atomic::Ordering;
+
+        let result = pipex!(
+            fail_fast vec![1, 2, 3]
+            => spawn async |x| {
+                if x == 2 {
+                    Err(format!("item {} failed", x))
+                } else {
+                    tokio::time::sleep(std::time::Duration::from_secs(60)).await;
+                    SPAWNED_FINISHED.fetch_add(1, Ordering::SeqCst);
+                    Ok(x)
+                }
+            }
+        );
+        assert_eq!(result.unwrap_err().input_index, 1);
+
+        // The other tasks were aborted rather than left running in the background
+        tokio::time::sleep(std::time::Duration::from_secs(120)).await;
+        assert_eq!(SPAWNED_FINISHED.load(Ordering::SeqCst), 0);
+    }
+
+    #[tokio::test(start_paused = true)]
+    async fn test_async_timeout_step() {
+        let result = pipex!(
+            vec![1u64, 2, 3, 4]
+            => async timeout(250ms) |x| {
+                tokio::time::sleep(std::time::Duration::from_millis(x * 100)).await;
+                Ok::<u64, String>(x * 10)
+            }
+        );
+
+        let timeout_error = time::TimeoutError::new(std::time::Duration::from_millis(250)).to_string();
+        assert_eq!(result, vec![Ok(10), Ok(20), Err(timeout_error.clone()), Err(timeout_error)]);
+    }
+
+    #[tokio::test(start_paused = true)]
+    async fn test_async_timeout_with_strategy() {
+        let deadline = std::time::Duration::from_secs(1);
+        let result = pipex!(
+            vec![4, 1, 5]
+            => async timeout(deadline) |x| {
+                if x == 5 {
+                    std::future::pending::<()>().await;
+                }
+                process_and_ignore(x).await
+            }
+        );
+
+        // The hung item times out and IgnoreHandler drops it like any other error
+        assert_eq!(result, vec![Ok(8), Ok(2)]);
+
+        let result = pipex!(
+            vec![5, 1]
+            => async timeout(deadline) |x| {
+                if x == 5 {
+                    std::future::pending::<()>().await;
+                }
+                process_and_ignore(x).await
+            }
+        );
+
+        // Still the case when the timeout is the first result of the step
+        assert_eq!(result, vec![Ok(2)]);
+    }
+
+    #[tokio::test(start_paused = true)]
+    async fn test_async_timeout_with_every_item_timed_out() {
+        let result = pipex!(
+            vec![5, 6]
+            => async timeout(1s) |x| {
+                std::future::pending::<()>().await;
+                process_and_ignore(x).await
+            }
+        );
+
+        // No item finished to bring IgnoreHandler along, so the timeouts are kept
+        let timeout_error = time::TimeoutError::new(std::time::Duration::from_secs(1)).to_string();
+        assert_eq!(result, vec![Err(timeout_error.clone()), Err(timeout_error)]);
+    }
+
+    #[test]
+    fn test_retry_step() {
+        let attempts = Mutex::new(HashMap::new());
+        let finished = std::sync::Arc::new(Mutex::new(Vec::new()));
+        let recorded = finished.clone();
+        let policy = retry::RetryPolicy::new(3)
+            .retry_if(|error: &String| !error.contains("permanent"))
+            .on_finish(move |attempts, error| recorded.lock().unwrap().push((attempts, error.cloned())));
+
+        let result = pipex!(
+            vec![1, 2, 3, 4]
+            => retry(policy) |x| {
+                let mut attempts = attempts.lock().unwrap();
+                let attempt = attempts.entry(x).or_insert(0);
+                *attempt += 1;
+                match x {
+                    // Succeeds on its second attempt
+                    2 if *attempt < 2 => Err("flaky".to_string()),
+                    3 => Err("always down".to_string()),
+                    4 => Err("permanent".to_string()),
+                    _ => Ok(x * 10),
+                }
+            }
+        );
+
+        assert_eq!(result, vec![
+            Ok(10),
+            Ok(20),
+            Err("always down (after 3 attempts)".to_string()),
+            // Rejected by retry_if, so it keeps its own error
+            Err("permanent".to_string()),
+        ]);
+        let attempts = attempts.into_inner().unwrap();
+        assert_eq!((attempts[&1], attempts[&2], attempts[&3], attempts[&4]), (1, 2, 3, 1));
+
+        // Every item reports its attempts, whether it succeeded, ran out or was not retried
+        assert_eq!(*finished.lock().unwrap(), vec![
+            (1, None),
+            (2, None),
+            (3, Some("always down".to_string())),
+            (1, Some("permanent".to_string())),
+        ]);
+    }
+
+    #[tokio::test(start_paused = true)]
+    async fn test_async_retry_step_backoff() {
+        use std::sync::atomic::{AtomicUsize, Ordering};
+
+        let calls = &AtomicUsize::new(0);
+        let start = tokio::time::Instant::now();
+        let result = pipex!(
+            vec![1, 3]
+            => async retry(retry::RetryPolicy::new(4).exponential_backoff(std::time::Duration::from_millis(100))) |x| {
+                calls.fetch_add(1, Ordering::SeqCst);
+                process_and_ignore(x).await
+            }
+        );
+
+        // Item 3 runs 4 times with 100ms, 200ms and 400ms between attempts, then IgnoreHandler drops it
+        assert_eq!(result, vec![Ok(2)]);
+        assert_eq!(calls.load(Ordering::SeqCst), 5);
+        assert_eq!(start.elapsed(), std::time::Duration::from_millis(700));
+    }
+
+    #[tokio::test(start_paused = true)]
+    async fn test_async_rate_step() {
+        let start = tokio::time::Instant::now();
+        let started = &Mutex::new(Vec::new());
+        let result = pipex!(
+            vec![1, 2, 3, 4, 5]
+            => async rate(10/s) |x| {
+                started.lock().unwrap().push(start.elapsed().as_millis());
+                Ok::<i32, String>(x)
+            }
+        );
+
+        assert_eq!(result.len(), 5);
+        // One permit every 100ms, the first one straight away
+        let started = started.lock().unwrap().clone();
+        for (i, millis) in started.iter().enumerate() {
+            assert!(millis.abs_diff(i as u128 * 100) <= 1, "item {} started at {}ms", i, millis);
+        }
+    }
+
+    #[tokio::test(start_paused = true)]
+    async fn test_shared_rate_limiter() {
+        let limiter = rate::RateLimiter::named(
+            "test_shared_rate_limiter",
+            rate::RateLimiter::new(20, std::time::Duration::from_secs(1)),
+        );
+        let start = tokio::time::Instant::now();
+
+        let (first, second) = tokio::join!(
+            async { pipex!(vec![1, 2, 3] => async rate(limiter) |x| { Ok::<i32, String>(x) }) },
+            async {
+                let same_limiter = rate::RateLimiter::named(
+                    "test_shared_rate_limiter",
+                    rate::RateLimiter::new(1, std::time::Duration::from_secs(1)),
+                );
+                pipex!(vec![4, 5, 6] => async rate(same_limiter) |x| { process_and_ignore(x).await })
+            }
+        );
+
+        // Six permits at 20/s through one bucket take 250ms, whichever pipeline asked
+        assert_eq!(first, vec![Ok(1), Ok(2), Ok(3)]);
+        assert_eq!(second, vec![Ok(8), Ok(10), Ok(12)]);
+        let elapsed = start.elapsed().as_millis();
+        assert!(elapsed.abs_diff(250) <= 1, "took {}ms", elapsed);
+    }
+
+    static FLAKY_SERVICE_UP: std::sync::atomic::AtomicBool = std::sync::atomic::AtomicBool::new(false);
+    static FLAKY_SERVICE_CALLS: std::sync::atomic::AtomicUsize = std::sync::atomic::AtomicUsize::new(0);
+
+    #[circuit_breaker(failures = 2, reset = "1s")]
+    async fn call_flaky_service(x: i32) -> Result<i32, String> {
+        use std::sync::atomic::Ordering;
+        FLAKY_SERVICE_CALLS.fetch_add(1, Ordering::SeqCst);
+        if FLAKY_SERVICE_UP.load(Ordering::SeqCst) {
+            Ok(x * 10)
+        } else {
+            Err(format!("service down for {}", x))
+        }
+    }
+
+    #[circuit_breaker(failures = 1, reset = "1m")]
+    #[error_strategy(IgnoreHandler)]
+    async fn always_down_and_ignored(x: i32) -> Result<i32, String> {
+        Err(format!("down for {}", x))
+    }
+
+    #[circuit_breaker(failures = 1, reset = "1s")]
+    async fn add_pair((a, b): (i32, i32), _: &str) -> Result<i32, String> {
+        Ok(a + b)
+    }
+
+    #[tokio::test(start_paused = true)]
+    async fn test_circuit_breaker_attribute() {
+        use std::sync::atomic::Ordering;
+        let open_error = circuit::CircuitOpenError { name: "call_flaky_service" }.to_string();
+
+        // Two failures open the breaker, later items fail fast without calling the service
+        let result = pipex!(vec![1, 2, 3, 4] => async |x| { call_flaky_service(x).await });
+        assert_eq!(result, vec![
+            Err("service down for 1".to_string()),
+            Err("service down for 2".to_string()),
+            Err(open_error.clone()),
+            Err(open_error.clone()),
+        ]);
+        assert_eq!(FLAKY_SERVICE_CALLS.load(Ordering::SeqCst), 2);
+
+        // The state is shared with the next run, which still fails fast during the cooldown
+        FLAKY_SERVICE_UP.store(true, Ordering::SeqCst);
+        let result = pipex!(vec![5] => async |x| { call_flaky_service(x).await });
+        assert_eq!(result, vec![Err(open_error)]);
+        assert_eq!(FLAKY_SERVICE_CALLS.load(Ordering::SeqCst), 2);
+
+        // After the cooldown a successful probe closes the breaker again
+        tokio::time::sleep(std::time::Duration::from_secs(1)).await;
+        let result = pipex!(vec![6, 7] => async |x| { call_flaky_service(x).await });
+        assert_eq!(result, vec![Ok(60), Ok(70)]);
+        assert_eq!(FLAKY_SERVICE_CALLS.load(Ordering::SeqCst), 4);
+    }
+
+    #[tokio::test]
+    async fn test_circuit_breaker_with_error_strategy() {
+        // Breaker-open errors go through IgnoreHandler like the failures that caused them
+        let result = pipex!(
+            vec![1, 2, 3]
+            => async |x| { always_down_and_ignored(x).await }
+        );
+        assert!(result.is_empty());
+    }
+
+    #[tokio::test]
+    async fn test_circuit_breaker_with_destructured_params() {
+        let result = pipex!(vec![(1, 2), (3, 4)] => async |pair| { add_pair(pair, "unused").await });
+        assert_eq!(result, vec![Ok(3), Ok(7)]);
+    }
+
+    #[tokio::test(start_paused = true)]
+    async fn test_circuit_breaker_ignores_late_and_dropped_calls() {
+        use std::time::Duration;
+        let breaker = circuit::CircuitBreaker::new("slow_service", 1, Duration::from_secs(1));
+
+        // A slow call admitted before the breaker opened does not close it by succeeding late
+        let slow = breaker.call(async {
+            tokio::time::sleep(Duration::from_millis(100)).await;
+            Ok::<i32, String>(1)
+        });
+        let failing = breaker.call(async { Err::<i32, String>("down".to_string()) });
+        assert_eq!(tokio::join!(slow, failing), (Ok(1), Err("down".to_string())));
+        assert!(breaker.is_open());
+
+        // A probe dropped by a timeout is not a failure, the next call probes instead
+        tokio::time::sleep(Duration::from_secs(1)).await;
+        let probe = breaker.call(std::future::pending::<Result<i32, String>>());
+        assert!(tokio::time::timeout(Duration::from_millis(10), probe).await.is_err());
+        assert!(!breaker.is_open());
+        assert_eq!(breaker.call(async { Ok::<i32, String>(2) }).await, Ok(2));
+
+        // Nor is a call dropped while the breaker is closed
+        let call = breaker.call(std::future::pending::<Result<i32, String>>());
+        assert!(tokio::time::timeout(Duration::from_millis(10), call).await.is_err());
+        assert!(!breaker.is_open());
+    }
+
+    #[test]
+    fn test_recover_and_map_err_steps() {
+        #[derive(Debug, PartialEq)]
+        enum LookupError {
+            Missing(String),
+        }
+
+        let result = pipex!(
+            vec![1, 2, 3, 4]
+            => |x| if x % 2 == 0 { Err(format!("no entry for {}", x)) } else { Ok(x * 10) }
+            => recover |e| if e.contains('2') { Ok(-1) } else { Err(e) }
+            => map_err |e| LookupError::Missing(e)
+        );
+
+        assert_eq!(result, vec![
+            Ok(10),
+            Ok(-1),
+            Ok(30),
+            Err(LookupError::Missing("no entry for 4".to_string())),
+        ]);
+    }
+
+    #[tokio::test]
+    async fn test_async_recover_and_map_err_steps() {
+        let result = pipex!(
+            vec![1, 2, 3]
+            => async |x| { process_and_collect(x).await }
+            => map_err async |e| { format!("item failed: {}", e) }
+            => recover async |e| {
+                tokio::task::yield_now().await;
+                if e == "item failed: failed on 3" { Ok(0) } else { Err(e) }
+            }
+        );
+
+        assert_eq!(result, vec![Ok(2), Ok(4), Ok(0)]);
+    }
+
+    // Deliberately neither Clone nor String-like
+    #[derive(Debug)]
+    enum LookupError {
+        NotFound(i32),
+        TimedOut(time::TimeoutError),
+    }
+
+    impl From<time::TimeoutError> for LookupError {
+        fn from(error: time::TimeoutError) -> Self {
+            LookupError::TimedOut(error)
+        }
+    }
+
+    #[error_strategy(CollectHandler)]
+    async fn typed_lookup(x: i32) -> Result<i32, LookupError> {
+        if x == 2 { Err(LookupError::NotFound(x)) } else { Ok(x) }
+    }
+
+    #[tokio::test(start_paused = true)]
+    async fn test_typed_errors_survive_all_steps() {
+        let result = pipex!(
+            vec![1, 2, 3, 4]
+            => async timeout(1s) |x| {
+                if x == 4 {
+                    std::future::pending::<()>().await;
+                }
+                typed_lookup(x).await
+            }
+            => ||| |x| Ok(x + 1)
+            => |x| Ok(x * 10)
+        );
+
+        assert_eq!(result.len(), 4);
+        assert!(matches!(result[0], Ok(20)));
+        assert!(matches!(result[1], Err(LookupError::NotFound(2))));
+        assert!(matches!(result[2], Ok(40)));
+        assert!(matches!(&result[3], Err(LookupError::TimedOut(e)) if e.duration == std::time::Duration::from_secs(1)));
+    }
+
+    #[test]
+    fn test_boxed_errors_keep_their_source() {
+        type BoxError = Box<dyn std::error::Error + Send + Sync>;
+
+        let result = pipex!(
+            vec!["1", "x", "3"]
+            => |s| s.parse::<i32>().map_err(BoxError::from)
+            => |x| if x > 2 { Err(std::io::Error::other("too big").into()) } else { Ok(x) }
+        );
+
+        assert_eq!(*result[0].as_ref().unwrap(), 1);
+        let parse_error = result[1].as_ref().unwrap_err();
+        assert!(parse_error.downcast_ref::<std::num::ParseIntError>().is_some());
+        let io_error = result[2].as_ref().unwrap_err();
+        assert_eq!(io_error.downcast_ref::<std::io::Error>().unwrap().to_string(), "too big");
+    }
+
+    #[test]
+    fn test_traced_pipeline_errors() {
+        use error::PipelineError;
+
+        let result = pipex!(
+            traced vec![1, 2, 3, 4, 5, 6]
+            => ? |x| *x != 2
+            => "validate": |x| if x % 3 == 0 { Err(format!("{} is divisible by 3", x)) } else { Ok(x) }
+            => expand |x| vec![x, x * 100]
+            => |x| if x == 500 { Err("too big".to_string()) } else { Ok(x) }
+            => map_err |e| e.to_uppercase()
+        );
+
+        let error = |stage, stage_name, input_index, cause: &str| {
+            Err(PipelineError { stage, stage_name, input_index, cause: cause.to_string() })
+        };
+        assert_eq!(result, vec![
+            Ok(1),
+            Ok(100),
+            error(1, Some("validate"), 2, "3 IS DIVISIBLE BY 3"),
+            Ok(4),
+            Ok(400),
+            Ok(5),
+            error(3, None, 4, "TOO BIG"),
+            error(1, Some("validate"), 5, "6 IS DIVISIBLE BY 3"),
+        ]);
+    }
+
+    #[test]
+    fn test_traced_pipeline_through_batch_and_unbatch() {
+        use error::PipelineError;
+
+        let result = pipex!(
+            traced vec![10, 20, 30, 40, 50]
+            => batch(2)
+            => |batch| Ok::<_, String>(batch)
+            => unbatch
+            => |x| if x == 40 { Err(format!("{} is too big", x)) } else { Ok(x) }
+        );
+
+        // The error points at the item that failed, not at the first item of its batch
+        assert_eq!(result, vec![
+            Ok(10),
+            Ok(20),
+            Ok(30),
+            Err(PipelineError { stage: 3, stage_name: None, input_index: 3, cause: "40 is too big".to_string() }),
+            Ok(50),
+        ]);
+    }
+
+    #[tokio::test(start_paused = true)]
+    async fn test_traced_pipeline_with_strategies() {
+        use error::PipelineError;
+
+        // IgnoreHandler drops the item from input 2, later errors still point at their own inputs
+        let result = pipex!(
+            traced vec![1, 2, 3, 4, 5]
+            => async |x| { process_and_ignore(x).await }
+            => "fetch": async timeout(1s) |x| {
+                if x == 8 {
+                    std::future::pending::<()>().await;
+                }
+                Ok::<_, String>(x)
+            }
+            => ||| |x| if x == 10 { Err("ten".to_string()) } else { Ok(x) }
+        );
+
+        assert_eq!(result, vec![
+            Ok(2),
+            Ok(4),
+            Err(PipelineError { stage: 1, stage_name: Some("fetch"), input_index: 3, cause: "timed out after 1s".to_string() }),
+            Err(PipelineError { stage: 2, stage_name: None, input_index: 4, cause: "ten".to_string() }),
+        ]);
+    }
+
+    #[test]
+    fn test_fail_fast_pipeline() {
+        use std::sync::atomic::{AtomicUsize, Ordering};
+        use error::PipelineError;
+
+        let parse_calls = &AtomicUsize::new(0);
+        let later_calls = &AtomicUsize::new(0);
+        let result = pipex!(
+            fail_fast vec![1, 2, 3, 4, 5]
+            => "parse": |x| {
+                parse_calls.fetch_add(1, Ordering::SeqCst);
+                if x == 3 { Err(format!("bad item {}", x)) } else { Ok(x) }
+            }
+            => ||| |x| {
+                later_calls.fetch_add(1, Ordering::SeqCst);
+                Ok::<_, String>(x * 10)
+            }
+        );
+
+        assert_eq!(result, Err(PipelineError {
+            stage: 0,
+            stage_name: Some("parse"),
+            input_index: 2,
+            cause: "bad item 3".to_string(),
+        }));
+        assert_eq!(parse_calls.load(Ordering::SeqCst), 3);
+        assert_eq!(later_calls.load(Ordering::SeqCst), 0);
+
+        let result = pipex!(
+            fail_fast vec![1, 2, 3]
+            => ||| |x| Ok::<_, String>(x * 2)
+            => expand |x| vec![x, x + 1]
+        );
+        assert_eq!(result, Ok(vec![2, 3, 4, 5, 6, 7]));
+    }
+
+    #[tokio::test(start_paused = true)]
+    async fn test_fail_fast_pipeline_cancels_in_flight_futures() {
+        use std::sync::atomic::{AtomicUsize, Ordering};
+
+        let finished = &AtomicUsize::new(0);
+        let result = pipex!(
+            fail_fast vec![1, 2, 3]
+            => async |x| {
+                if x == 2 {
+                    Err(format!("item {} failed", x))
+                } else {
+                    tokio::time::sleep(std::time::Duration::from_secs(60)).await;
+                    finished.fetch_add(1, Ordering::SeqCst);
+                    Ok(x)
+                }
+            }
+            => async |x| {
+                finished.fetch_add(1, Ordering::SeqCst);
+                Ok::<_, String>(x)
+            }
+        );
+
+        let error = result.unwrap_err();
+        assert_eq!((error.stage, error.input_index), (0, 1));
+        assert_eq!(error.cause, "item 2 failed");
+        assert_eq!(finished.load(Ordering::SeqCst), 0);
+    }
+
+    #[tokio::test(start_paused = true)]
+    async fn test_fail_fast_async_batch_cancels_other_batches() {
+        use std::sync::atomic::{AtomicUsize, Ordering};
+
+        let finished_batches = &AtomicUsize::new(0);
+        let result = pipex!(
+            fail_fast (1..=12).collect::<Vec<i32>>()
+            => async batch(2) |xs| {
+                if xs.contains(&1) {
+                    xs.into_iter()
+                        .map(|x| if x == 2 { Err(format!("bad item {}", x)) } else { Ok(x) })
+                        .collect::<Vec<_>>()
+                } else {
+                    tokio::time::sleep(std::time::Duration::from_millis(50)).await;
+                    finished_batches.fetch_add(1, Ordering::SeqCst);
+                    xs.into_iter().map(Ok).collect()
+                }
+            }
+        );
+
+        let error = result.unwrap_err();
+        assert_eq!((error.input_index, error.cause.as_str()), (1, "bad item 2"));
+        assert_eq!(finished_batches.load(Ordering::SeqCst), 0);
+    }
+
+    #[tokio::test]
+    async fn test_indexed_pipeline() {
+        // IgnoreHandler drops input 2, the rest still line up with their inputs
+        let result = pipex!(
+            indexed vec![1, 2, 3, 4, 5]
+            => async |x| { process_and_ignore(x).await }
+            => ||| |x| Ok(x + 1)
+        );
+        assert_eq!(result, vec![(0, Ok(3)), (1, Ok(5)), (3, Ok(9)), (4, Ok(11))]);
+
+        // Custom strategies are registered for the indexed types
+        let result = pipex!(
+            indexed vec![1, 2, 3, 4, 5]
+            => async |x| { process_with_first_error(x).await }
+        );
+        assert_eq!(result, vec![(2, Err("failed on 3".to_string()))]);
+    }
+
+    #[test]
+    fn test_indexed_pipeline_through_filter_and_expand() {
+        let result = pipex!(
+            indexed vec!["a b", "", "c", "d e f"]
+            => ? |line| !line.is_empty()
+            => expand |line| line.split(' ').collect::<Vec<_>>()
+            => |word| if word == "e" { Err(format!("bad word {}", word)) } else { Ok(word.to_uppercase()) }
+        );
+
+        assert_eq!(result, vec![
+            (0, Ok("A".to_string())),
+            (0, Ok("B".to_string())),
+            (2, Ok("C".to_string())),
+            (3, Ok("D".to_string())),
+            (3, Err("bad word e".to_string())),
+            (3, Ok("F".to_string())),
+        ]);
+    }
+
+    #[error_strategy(IgnoreHandler)]
+    fn drop_batch_with_30(batch: Vec<i32>) -> Result<Vec<i32>, String> {
+        if batch.contains(&30) { Err("batch contains 30".to_string()) } else { Ok(batch) }
+    }
+
+    #[test]
+    fn test_indexed_pipeline_through_batch_and_unbatch() {
+        let result = pipex!(
+            indexed vec![10, 20, 30, 40, 50]
+            => batch(2)
+            => |batch| Ok::<_, String>(batch)
+            => unbatch
+        );
+        assert_eq!(result, vec![(0, Ok(10)), (1, Ok(20)), (2, Ok(30)), (3, Ok(40)), (4, Ok(50))]);
+
+        // Items keep their own index after a strategy has dropped a whole batch
+        let result = pipex!(
+            indexed vec![10, 20, 30, 40, 50]
+            => batch(2)
+            => |batch| drop_batch_with_30(batch)
+            => unbatch
+        );
+        assert_eq!(result, vec![(0, Ok(10)), (1, Ok(20)), (4, Ok(50))]);
+    }
+
+    #[tokio::test]
+    async fn test_unordered_async_pipeline() {
+        use std::time::Duration;
+
+        let result = pipex!(
+            vec![1, 2, 3]
+            => async unordered |x| {
+                // The first item is the slowest, so it must finish last
+                tokio::time::sleep(Duration::from_millis(if x == 1 { 50 } else { 0 })).await;
+                Ok::<i32, String>(x * 10)
+            }
+        );
+
+        let values: Vec<i32> = result.into_iter().map(|r| r.unwrap()).collect();
+        assert_eq!(values.len(), 3);
+        assert_eq!(values[2], 10);
     }
 
     #[tokio::test]
-    async fn test_strategy_failfast() {
+    async fn test_unordered_indexed_with_strategies() {
         let result = pipex!(
-            vec![1, 2, 3, 4, 5, 3]
-            => async |x| { process_with_failfast(x).await }
+            vec![1, 2, 3, 4, 5]
+            => async unordered(2) indexed |x| { process_and_ignore(x).await }
         );
-        
-        // Should fail fast - return only errors
+
+        // IgnoreHandler drops item 3; tags allow restoring input order
+        let mut tagged: Vec<(usize, i32)> = result.into_iter().map(|r| r.unwrap()).collect();
+        tagged.sort();
+        assert_eq!(tagged, vec![(0, 2), (1, 4), (3, 8), (4, 10)]);
+
+        let result = pipex!(
+            vec![1, 2, 3, 4, 3]
+            => async unordered indexed |x| { process_with_failfast(x).await }
+        );
+
+        // FailFastHandler keeps only the errors
+        assert_eq!(result.len(), 2);
         assert!(result.iter().all(|r| r.is_err()));
-        assert_eq!(result.len(), 2); // Two instances of 3
-        eprintln!("result: {:?}", result);
-    }
 
-    #[tokio::test]
-    async fn test_custom_first_error_handler() {
+        // An indexed pipeline tags values with their input index, even after a filter
         let result = pipex!(
-            vec![1, 2, 3, 4, 5]
-            => async |x| { process_with_first_error(x).await }
+            indexed vec![1, 2, 3, 4]
+            => ? |x| *x != 1
+            => async unordered indexed |x| { Ok::<_, String>(x * 10) }
         );
-        
-        // Should return only the first error
-        assert_eq!(result.len(), 1);
-        assert!(result[0].is_err());
-        eprintln!("FirstErrorHandler result: {:?}", result);
+        let mut tagged: Vec<(usize, i32)> = result.into_iter().map(|(_, r)| r.unwrap()).collect();
+        tagged.sort();
+        assert_eq!(tagged, vec![(1, 20), (2, 30), (3, 40)]);
     }
 
     #[cfg(feature = "parallel")]
@@ -324,6 +1074,48 @@ mod tests {
         // assert_eq!(values, vec![3, 5, 7, 9, 11]);
     }
 
+    static ASYNC_PARALLEL_STARTED: std::sync::atomic::AtomicBool = std::sync::atomic::AtomicBool::new(false);
+    static ASYNC_PARALLEL_FINISHED: std::sync::atomic::AtomicUsize = std::sync::atomic::AtomicUsize::new(0);
+    static ASYNC_PARALLEL_RELEASED: (Mutex<bool>, std::sync::Condvar) = (Mutex::new(false), std::sync::Condvar::new());
+
+    #[tokio::test]
+    async fn test_async_parallel_step() {
+        use std::sync::atomic::Ordering;
+
+        let pipeline = async {
+            pipex!(
+                vec![1, 2, 3, 4, 5]
+                => async ||| |x| {
+                    // Hold every item until the other task has looked, giving up after a while
+                    // so that a blocked runtime fails the test instead of hanging it
+                    ASYNC_PARALLEL_STARTED.store(true, Ordering::SeqCst);
+                    let (released, condvar) = &ASYNC_PARALLEL_RELEASED;
+                    let _released = condvar
+                        .wait_timeout_while(released.lock().unwrap(), std::time::Duration::from_secs(1), |released| !*released)
+                        .unwrap();
+                    ASYNC_PARALLEL_FINISHED.fetch_add(1, Ordering::SeqCst);
+                    sync_process_and_ignore(x)
+                }
+            )
+        };
+        let other_task = async {
+            while !ASYNC_PARALLEL_STARTED.load(Ordering::SeqCst) {
+                tokio::task::yield_now().await;
+            }
+            let finished = ASYNC_PARALLEL_FINISHED.load(Ordering::SeqCst);
+            let (released, condvar) = &ASYNC_PARALLEL_RELEASED;
+            *released.lock().unwrap() = true;
+            condvar.notify_all();
+            finished
+        };
+        let (result, finished_meanwhile) = tokio::join!(pipeline, other_task);
+
+        // The other task ran while the rayon job was holding every item
+        assert_eq!(finished_meanwhile, 0);
+        let values: Vec<i32> = result.into_iter().map(|r| r.unwrap()).collect();
+        assert_eq!(values, vec![2, 4, 8, 10]);
+    }
+
     // Test sync function with strategy decorator
     #[error_strategy(IgnoreHandler)]
     fn sync_process_and_ignore(x: i32) -> Result<i32, String> {
@@ -352,6 +1144,76 @@ mod tests {
         assert_eq!(actual_values, expected_values); 
     }
 
+    #[error_strategy(LogAndIgnoreHandler)]
+    fn parse_or_panic(s: &str) -> Result<i32, String> {
+        Ok(s.parse().expect("not a number"))
+    }
+
+    #[test]
+    fn test_catch_steps() {
+        let result = pipex!(
+            vec![1, 2, 0, 4]
+            => catch |x| if x == 0 { panic!("division by zero") } else { Ok::<_, String>(12 / x) }
+            => catch ||| |x| {
+                assert!(x != 6, "six is not allowed");
+                Ok::<_, String>(x)
+            }
+        );
+        assert_eq!(result, vec![
+            Ok(12),
+            Err("panicked: six is not allowed".to_string()),
+            Err("panicked: division by zero".to_string()),
+            Ok(3),
+        ]);
+
+        // The step's strategy handles caught panics like any other error
+        let result = pipex!(
+            vec!["1", "x", "3"]
+            => catch |s| parse_or_panic(s)
+        );
+        assert_eq!(result, vec![Ok(1), Ok(3)]);
+    }
+
+    #[tokio::test]
+    async fn test_async_catch_step() {
+        let result = pipex!(
+            vec![1, 2, 3]
+            => catch async |x| {
+                tokio::task::yield_now().await;
+                if x == 2 {
+                    panic!("lost item {}", x);
+                }
+                Ok::<_, String>(x * 10)
+            }
+        );
+        assert_eq!(result, vec![Ok(10), Err("panicked: lost item 2".to_string()), Ok(30)]);
+    }
+
+    static BLOCKING_IN_FLIGHT: std::sync::atomic::AtomicUsize = std::sync::atomic::AtomicUsize::new(0);
+    static BLOCKING_MAX_IN_FLIGHT: std::sync::atomic::AtomicUsize = std::sync::atomic::AtomicUsize::new(0);
+
+    #[tokio::test]
+    async fn test_blocking_step() {
+        use std::sync::atomic::Ordering;
+
+        let result = pipex!(
+            (1..=10).collect::<Vec<i32>>()
+            => blocking(2) |x| {
+                let current = BLOCKING_IN_FLIGHT.fetch_add(1, Ordering::SeqCst) + 1;
+                BLOCKING_MAX_IN_FLIGHT.fetch_max(current, Ordering::SeqCst);
+                std::thread::sleep(std::time::Duration::from_millis(5));
+                BLOCKING_IN_FLIGHT.fetch_sub(1, Ordering::SeqCst);
+                Ok::<_, String>(x)
+            }
+            => blocking |x| sync_process_and_ignore(x)
+        );
+
+        // At most 2 closures at once, IgnoreHandler drops item 3 as in a sync step
+        assert!(BLOCKING_MAX_IN_FLIGHT.load(Ordering::SeqCst) <= 2);
+        let values: Vec<i32> = result.into_iter().map(|r| r.unwrap()).collect();
+        assert_eq!(values, vec![2, 4, 8, 10, 12, 14, 16, 18, 20]);
+    }
+
     #[tokio::test]
     async fn test_mixed_sync_and_async_pipeline() {
         // Using an existing async function with its own error strategy
@@ -374,6 +1236,489 @@ mod tests {
         assert_eq!(actual_results.len(), 4);
     }
 
+    #[test]
+    fn test_filter_step() {
+        let result = pipex!(
+            vec![1, 2, 3, 4, 5, 6]
+            => |x| if x == 5 { Err("failed on 5".to_string()) } else { Ok(x) }
+            => ? |x| x % 2 == 0
+            => |x| Ok::<i32, String>(x * 10)
+        );
+
+        // Odd successes are filtered out, the error from the earlier stage is kept in place
+        assert_eq!(result, vec![Ok(20), Ok(40), Err("failed on 5".to_string()), Ok(60)]);
+    }
+
+    #[cfg(feature = "parallel")]
+    #[test]
+    fn test_parallel_filter_step() {
+        let result = pipex!(
+            (1..=10).collect::<Vec<i32>>()
+            => ||| |x| Ok::<i32, String>(x * x)
+            => ? ||| |x| *x > 50
+        );
+
+        let values: Vec<i32> = result.into_iter().map(|r| r.unwrap()).collect();
+        assert_eq!(values, vec![64, 81, 100]);
+    }
+
+    #[tokio::test]
+    async fn test_async_filter_step() {
+        let result = pipex!(
+            vec![1, 2, 3, 4, 5]
+            => async |x| { simple_double(x).await }
+            => ? async |x| { tokio::task::yield_now().await; *x > 4 }
+        );
+
+        assert_eq!(result, vec![Err("failed on 3".to_string()), Ok(8), Ok(10)]);
+    }
+
+    #[error_strategy(IgnoreHandler)]
+    async fn repeat_or_ignore(x: i32) -> Result<Vec<i32>, String> {
+        if x == 3 {
+            Err("failed on 3".to_string())
+        } else {
+            Ok(vec![x; x as usize])
+        }
+    }
+
+    #[tokio::test]
+    async fn test_keep_steps() {
+        let result = pipex!(
+            vec!["a", "bb", ""]
+            => keep |s| if s.is_empty() { Err("empty".to_string()) } else { Ok(s.len()) }
+            => keep ||| |pair| Ok(pair.1 * 10)
+        );
+        assert_eq!(result, vec![
+            Ok((("a", 1), 10)),
+            Ok((("bb", 2), 20)),
+            Err("empty".to_string()),
+        ]);
+
+        // The strategy of the async closure still applies
+        let result = pipex!(
+            vec![1, 2, 3, 4]
+            => keep async |x| { process_and_ignore(*x).await }
+        );
+        assert_eq!(result, vec![Ok((1, 2)), Ok((2, 4)), Ok((4, 8))]);
+    }
+
+    #[test]
+    fn test_expand_step() {
+        let result = pipex!(
+            vec![1, 2, 3, 4]
+            => expand |x| if x == 3 { Err("failed on 3") } else { Ok(0..x) }
+            => |x| Ok::<i32, &str>(x * 10)
+        );
+
+        // Each item expands into x values, the failed item becomes a single error
+        assert_eq!(result.len(), 8);
+        assert_eq!(result[3], Err("failed on 3"));
+        let values: Vec<i32> = result.into_iter().filter_map(|r| r.ok()).collect();
+        assert_eq!(values, vec![0, 0, 10, 0, 10, 20, 30]);
+
+        // Nothing here can fail, so the error type comes from the annotation
+        let result: Vec<Result<&str, String>> = pipex!(
+            vec!["a b", "", "c"]
+            => expand |line| line.split_whitespace().collect::<Vec<_>>()
+        );
+        let words: Vec<&str> = result.into_iter().map(|r| r.unwrap()).collect();
+        assert_eq!(words, vec!["a", "b", "c"]);
+    }
+
+    #[cfg(feature = "parallel")]
+    #[test]
+    fn test_parallel_expand_step() {
+        let result = pipex!(
+            vec![1, 2, 3]
+            => expand ||| |x| Ok::<Vec<i32>, String>(vec![x, -x])
+        );
+
+        let values: Vec<i32> = result.into_iter().map(|r| r.unwrap()).collect();
+        assert_eq!(values, vec![1, -1, 2, -2, 3, -3]);
+    }
+
+    #[tokio::test]
+    async fn test_async_expand_with_strategy() {
+        let result = pipex!(
+            vec![1, 2, 3]
+            => expand async |x| { repeat_or_ignore(x).await }
+        );
+
+        // IgnoreHandler drops the failed item before flattening
+        let values: Vec<i32> = result.into_iter().map(|r| r.unwrap()).collect();
+        assert_eq!(values, vec![1, 2, 2]);
+    }
+
+    #[tokio::test]
+    async fn test_batch_and_unbatch_steps() {
+        let result = pipex!(
+            vec![1, 2, 3, 4, 5, 6, 7]
+            => async |x| { simple_double(x).await }
+            => batch(2)
+            => |chunk| Ok::<Vec<i32>, String>(chunk.into_iter().map(|x| x + 1).collect())
+        );
+
+        // The error from item 3 is forwarded ahead of the batch it interrupted
+        assert_eq!(result, vec![
+            Ok(vec![3, 5]),
+            Err("failed on 3".to_string()),
+            Ok(vec![9, 11]),
+            Ok(vec![13, 15]),
+        ]);
+
+        let result = pipex!(
+            vec![1, 2, 3, 4, 5, 6, 7]
+            => async |x| { simple_double(x).await }
+            => batch(2)
+            => |chunk| Ok::<Vec<i32>, String>(chunk.into_iter().map(|x| x + 1).collect())
+            => unbatch
+            => |x| Ok::<i32, String>(x * 10)
+        );
+        assert_eq!(result.len(), 7);
+        assert!(result[2].is_err());
+        let values: Vec<i32> = result.into_iter().filter_map(|r| r.ok()).collect();
+        assert_eq!(values, vec![30, 50, 90, 110, 130, 150]);
+    }
+
+    async fn bulk_double(xs: Vec<i32>) -> Vec<PipexResult<i32, String>> {
+        xs.into_iter()
+            .map(|x| PipexResult::new(
+                if x == 3 { Err("failed on 3".to_string()) } else { Ok(x * 2) },
+                "IgnoreHandler",
+            ))
+            .collect()
+    }
+
+    #[tokio::test]
+    async fn test_async_batch_scatters_results() {
+        use std::sync::atomic::{AtomicUsize, Ordering};
+
+        let calls = &AtomicUsize::new(0);
+        let result = pipex!(
+            vec![1, 2, 3, 4, 5]
+            => async batch(2) |xs| {
+                calls.fetch_add(1, Ordering::SeqCst);
+                bulk_double(xs).await
+            }
+        );
+
+        // Three bulk calls, and IgnoreHandler still drops item 3 individually
+        assert_eq!(calls.load(Ordering::SeqCst), 3);
+        assert_eq!(result, vec![Ok(2), Ok(4), Ok(8), Ok(10)]);
+    }
+
+    #[tokio::test]
+    async fn test_async_keyed_batch() {
+        use std::collections::HashMap;
+
+        let result = pipex!(
+            vec![1, 2, 3, 4]
+            => async |x| { simple_double(x).await }
+            => async batch(10) keyed |xs| {
+                // The bulk call has no entry for 8
+                xs.into_iter()
+                    .filter(|x| *x != 8)
+                    .map(|x| (x, Ok::<String, String>(format!("item-{}", x))))
+                    .collect::<HashMap<_, _>>()
+            }
+        );
+
+        assert_eq!(result.len(), 4);
+        assert_eq!(result[0], Ok("item-2".to_string()));
+        assert_eq!(result[1], Ok("item-4".to_string()));
+        assert_eq!(result[2], Err("failed on 3".to_string()));
+        assert!(result[3].as_ref().unwrap_err().contains("no result for 8"));
+    }
+
+    #[tokio::test]
+    async fn test_async_keyed_batch_with_duplicate_keys() {
+        use std::collections::HashMap;
+
+        let keys_seen = &Mutex::new(Vec::new());
+        let result = pipex!(
+            vec![1, 1, 2, 1]
+            => async batch(10) keyed |xs| {
+                keys_seen.lock().unwrap().push(xs.clone());
+                xs.into_iter()
+                    .map(|x| (x, Ok::<i32, String>(x * 10)))
+                    .collect::<HashMap<_, _>>()
+            }
+        );
+
+        // Every item sharing a key gets its result, and the bulk call sees each key once
+        assert_eq!(result, vec![Ok(10), Ok(10), Ok(20), Ok(10)]);
+        assert_eq!(*keys_seen.lock().unwrap(), vec![vec![1, 2]]);
+    }
+
+    #[tokio::test]
+    async fn test_fold_and_reduce_terminals() {
+        use std::collections::BTreeMap;
+
+        let histogram = pipex!(
+            vec!["a", "b", "a", "c", "a"]
+            => |word| Ok::<&str, String>(word)
+            => fold(BTreeMap::new(), |counts, word| {
+                let mut counts = counts;
+                *counts.entry(word).or_insert(0) += 1;
+                counts
+            })
+        );
+        assert_eq!(histogram.unwrap(), BTreeMap::from([("a", 3), ("b", 1), ("c", 1)]));
+
+        // Errors that survive the previous strategy stop the fold
+        let total = pipex!(
+            vec![1, 2, 3, 4]
+            => async |x| { simple_double(x).await }
+            => fold(0, |acc, x| acc + x)
+        );
+        assert_eq!(total, Err("failed on 3".to_string()));
+
+        // ...while IgnoreHandler drops them before the fold sees them
+        let total = pipex!(
+            vec![1, 2, 3, 4]
+            => async |x| { process_and_ignore(x).await }
+            => fold(0, |acc, x| acc + x)
+        );
+        assert_eq!(total, Ok(14));
+
+        let max = pipex!(
+            vec![3, 9, 4]
+            => |x| Ok::<i32, String>(x)
+            => reduce(|a, b| a.max(b))
+        );
+        assert_eq!(max, Ok(Some(9)));
+
+        let empty = pipex!(
+            Vec::<i32>::new()
+            => |x| Ok::<i32, String>(x)
+            => reduce(|a, b| a + b)
+        );
+        assert_eq!(empty, Ok(None));
+    }
+
+    #[cfg(feature = "parallel")]
+    #[test]
+    fn test_parallel_reduce_terminal() {
+        let sum = pipex!(
+            (1..=100).collect::<Vec<i64>>()
+            => ||| |x| Ok::<i64, String>(x * 2)
+            => ||| reduce(|a, b| a + b)
+        );
+        assert_eq!(sum, Ok(Some(10100)));
+
+        // The error of the first failing item is returned, even when a later one is reached sooner
+        let pool = rayon::ThreadPoolBuilder::new().num_threads(4).build().unwrap();
+        let sum = pool.install(|| pipex!(
+            (1..=1000).collect::<Vec<i64>>()
+            => ||| |x| if x == 10 || x == 990 { Err(format!("bad {}", x)) } else { Ok(x) }
+            => ||| reduce(|a, b| {
+                if b < 10 {
+                    std::thread::sleep(std::time::Duration::from_millis(20));
+                }
+                a + b
+            })
+        ));
+        assert_eq!(sum, Err("bad 10".to_string()));
+    }
+
+    #[tokio::test]
+    async fn test_collect_and_partition_terminals() {
+        use std::collections::{BTreeSet, HashMap};
+
+        let lookup: Result<HashMap<i32, i32>, String> = pipex!(
+            vec![1, 2, 4]
+            => async |x| { simple_double(x).await }
+            => |x| Ok::<(i32, i32), String>((x, x * x))
+            => collect
+        );
+        assert_eq!(lookup, Ok(HashMap::from([(2, 4), (4, 16), (8, 64)])));
+
+        let letters = pipex!(
+            vec!['c', 'a', 'b', 'a']
+            => |c| Ok::<char, String>(c)
+            => collect::<Result<BTreeSet<char>, String>>
+        );
+        assert_eq!(letters, Ok(BTreeSet::from(['a', 'b', 'c'])));
+
+        let (values, errors) = pipex!(
+            vec![1, 2, 3, 4]
+            => async |x| { simple_double(x).await }
+            => partition
+        );
+        assert_eq!(values, vec![2, 4, 8]);
+        assert_eq!(errors, vec!["failed on 3".to_string()]);
+
+        let all = pipex!(
+            vec![1, 2, 3, 4]
+            => async |x| { simple_double(x).await }
+            => ok_or_errors
+        );
+        assert_eq!(all, Err(vec!["failed on 3".to_string()]));
+
+        let all = pipex!(
+            vec![1, 2, 4]
+            => async |x| { simple_double(x).await }
+            => ok_or_errors
+        );
+        assert_eq!(all, Ok(vec![2, 4, 8]));
+    }
+
+    #[tokio::test]
+    async fn test_tap_step() {
+        use std::sync::Mutex;
+
+        let seen = Mutex::new(Vec::new());
+        let failures = Mutex::new(Vec::new());
+
+        let result = pipex!(
+            vec![1, 2, 3, 4]
+            => async |x| { simple_double(x).await }
+            => tap |x| seen.lock().unwrap().push(*x), on_err |e| failures.lock().unwrap().push(e.clone())
+            => tap |x| assert!(*x > 0)
+        );
+
+        // Items and errors pass through unchanged
+        assert_eq!(result, vec![Ok(2), Ok(4), Err("failed on 3".to_string()), Ok(8)]);
+        assert_eq!(*seen.lock().unwrap(), vec![2, 4, 8]);
+        assert_eq!(*failures.lock().unwrap(), vec!["failed on 3".to_string()]);
+
+        // Strategies applied by the previous step are kept as-is
+        let result = pipex!(
+            vec![1, 2, 3, 4]
+            => async |x| { process_and_ignore(x).await }
+            => tap |x| seen.lock().unwrap().push(*x)
+        );
+        assert_eq!(result, vec![Ok(2), Ok(4), Ok(8)]);
+    }
+
+    #[tokio::test]
+    async fn test_stream_pipeline_is_lazy() {
+        use futures::StreamExt;
+        use std::sync::atomic::{AtomicUsize, Ordering};
+
+        let processed = &AtomicUsize::new(0);
+        let results = pipex_stream!(
+            iter 1..
+            => |x| { processed.fetch_add(1, Ordering::SeqCst); Ok::<i32, String>(x) }
+            => async |x| { simple_double(x).await }
+            => ? |x| *x % 4 == 0
+        )
+        .take(3)
+        .collect::<Vec<_>>()
+        .await;
+
+        // Only as many items as needed were pulled from the unbounded input
+        assert_eq!(results, vec![Ok(4), Err("failed on 3".to_string()), Ok(8)]);
+        assert_eq!(processed.load(Ordering::SeqCst), 4);
+    }
+
+    #[tokio::test]
+    async fn test_stream_pipeline_with_strategies() {
+        use futures::StreamExt;
+
+        let input = futures::stream::iter(vec![1, 2, 3, 4, 5]);
+        let results = pipex_stream!(
+            input
+            => async(2) |x| { process_and_ignore(x).await }
+            => |x| Ok::<i32, String>(x + 1)
+        )
+        .collect::<Vec<_>>()
+        .await;
+
+        assert_eq!(results, vec![Ok(3), Ok(5), Ok(9), Ok(11)]);
+    }
+
+    #[cfg(feature = "parallel")]
+    #[tokio::test]
+    async fn test_stream_pipeline_parallel_step() {
+        use futures::StreamExt;
+        use std::sync::atomic::{AtomicUsize, Ordering};
+
+        let results = pipex_stream!(
+            iter vec![1, 2, 3, 4, 5]
+            => ||| |x| sync_process_and_ignore(x)
+            => tap |x| assert!(*x % 2 == 0)
+        )
+        .collect::<Vec<_>>()
+        .await;
+
+        assert_eq!(results, vec![Ok(2), Ok(4), Ok(8), Ok(10)]);
+
+        // Chunks run as rayon jobs, so other tasks make progress while they are busy
+        static FINISHED: AtomicUsize = AtomicUsize::new(0);
+        let stream = pipex_stream!(
+            iter vec![1, 2, 3]
+            => ||| |x| {
+                std::thread::sleep(std::time::Duration::from_millis(20));
+                FINISHED.fetch_add(1, Ordering::SeqCst);
+                Ok::<i32, String>(x)
+            }
+        )
+        .collect::<Vec<_>>();
+        let other_task = async {
+            tokio::task::yield_now().await;
+            FINISHED.load(Ordering::SeqCst)
+        };
+        let (results, finished_meanwhile) = tokio::join!(stream, other_task);
+
+        assert!(finished_meanwhile < 3);
+        assert_eq!(results, vec![Ok(1), Ok(2), Ok(3)]);
+    }
+
+    #[tokio::test]
+    async fn test_pipelined_stages_overlap() {
+        use futures::StreamExt;
+        use std::sync::Mutex;
+        use std::time::Duration;
+
+        static EVENTS: Mutex<Vec<(&str, i32)>> = Mutex::new(Vec::new());
+
+        let results = pipex_stream!(
+            pipelined(2) iter vec![1, 2, 3, 4, 5]
+            => async |x| {
+                tokio::time::sleep(Duration::from_millis(5)).await;
+                EVENTS.lock().unwrap().push(("fetch", x));
+                Ok::<i32, String>(x)
+            }
+            => |x| {
+                EVENTS.lock().unwrap().push(("parse", x));
+                Ok::<i32, String>(x * 10)
+            }
+        )
+        .collect::<Vec<_>>()
+        .await;
+
+        assert_eq!(results, vec![Ok(10), Ok(20), Ok(30), Ok(40), Ok(50)]);
+
+        // The second step started on item 1 before the first step finished item 5
+        let events = EVENTS.lock().unwrap();
+        let first_parse = events.iter().position(|e| *e == ("parse", 1)).unwrap();
+        let last_fetch = events.iter().position(|e| *e == ("fetch", 5)).unwrap();
+        assert!(first_parse < last_fetch, "events: {:?}", events);
+    }
+
+    #[tokio::test]
+    async fn test_pipelined_backpressure() {
+        use futures::StreamExt;
+        use std::sync::atomic::{AtomicUsize, Ordering};
+
+        static PULLED: AtomicUsize = AtomicUsize::new(0);
+
+        let results = pipex_stream!(
+            pipelined(1) iter 1..
+            => |x| { PULLED.fetch_add(1, Ordering::SeqCst); Ok::<i32, String>(x) }
+            => async |x| { tokio::task::yield_now().await; Ok::<i32, String>(x * 2) }
+        )
+        .take(3)
+        .collect::<Vec<_>>()
+        .await;
+
+        // Bounded channels keep the unbounded source from running ahead
+        assert_eq!(results, vec![Ok(2), Ok(4), Ok(6)]);
+        assert!(PULLED.load(Ordering::SeqCst) < 10);
+    }
+
     // === Pure Macro Tests ===
     
     #[pure]
@@ -433,6 +1778,7 @@ mod tests {
     // They should fail to compile if uncommented, demonstrating the macro's safety
     
     // Test case for demonstrating proper error messages when calling impure functions
+    #[allow(dead_code)]
     fn regular_impure_function(x: i32) -> i32 {
         // This function is not marked as #[pure], so it's considered impure
         x * 2
@@ -452,6 +1798,7 @@ mod tests {
     
     // === Triple Power: Error Strategy + Pure + Memoized ===
     
+    #[allow(clippy::assign_op_pattern)]
     #[pure]
     #[memoized(capacity = 200)]
     #[error_strategy(CollectHandler)]
@@ -474,6 +1821,7 @@ mod tests {
         Ok(result)
     }
     
+    #[allow(clippy::assign_op_pattern)]
     #[pure]
     #[memoized(capacity = 100)]
     fn expensive_computation(x: i32, y: i32) -> i32 {
@@ -486,6 +1834,7 @@ mod tests {
     }
     
     // Non-memoized version for comparison
+    #[allow(dead_code)]
     #[pure]
     fn fibonacci_regular(n: u64) -> u64 {
         if n <= 1 { 
@@ -796,7 +2145,7 @@ mod tests {
         
         // GPU version
         let gpu_start = Instant::now();
-        let gpu_result = pipex!(
+        let gpu_result: Vec<Result<f32, String>> = pipex!(
             data
             => gpu r#"
                 @group(0) @binding(0) var<storage, read> input: array<f32>;
@@ -886,7 +2235,8 @@ mod tests {
         // Test auto-transpilation with complex nested mathematical expressions
         let input_data = vec![0.5f32, 1.0, 1.5, 2.0];
         
-        let result = pipex!(
+        // Nothing in this pipeline can fail, so its error type has to be named
+        let result: Vec<Result<f32, String>> = pipex!(
             input_data
             => gpu ||| |x| (x * x + 1.0) * x.sin() + x.cos() * (x + 3.14159) - x.sqrt()
             // 🔥 Complex expression: mixed operations, multiple math functions, constants!
@@ -943,11 +2293,3 @@ mod tests {
     }
     */
 }
-
-// It's also good practice to explicitly re-export items that macros need,
-// especially if they are somewhat internal.
-// This makes the macro's dependency on $crate::traits::IntoPipelineItem robust.
-#[doc(hidden)]
-pub use traits::IntoPipelineItem as __InternalIntoPipelineItem; // Re-export for macro under a hidden name
-                                                                 // The macro can then use $crate::__InternalIntoPipelineItem
-                                                                 // OR, if the `traits` module is pub, $crate::traits::IntoPipelineItem works.
@@ -1,2 +1,2 @@
 pub mod pipex;
-pub use self::pipex::*;
\ No newline at end of file
+pub mod pipex_stream;
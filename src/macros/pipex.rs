@@ -9,69 +9,1105 @@
 /// 
 /// - `|x| expr` - Synchronous transformation
 /// - `async |x| { ... }` - Asynchronous operation  
+/// - `async(n) |x| { ... }` - Asynchronous operation with at most `n` futures in flight
+/// - `async timeout(500ms) |x| { ... }` - Asynchronous operation in which items still running after
+///   the deadline become a [`TimeoutError`](crate::time::TimeoutError); the deadline is a literal
+///   (`ns`, `us`, `ms`, `s`, `m`, `h`) or any `Duration` expression
+/// - `async rate(100/s) |x| { ... }` - Asynchronous operation in which items start no faster than the
+///   given rate (`/ms`, `/s`, `/m`, `/h`); `async rate(limiter)` takes permits from a shared
+///   [`RateLimiter`](crate::rate::RateLimiter) instead
+/// - `async unordered |x| { ... }` - Asynchronous operation emitting results in completion order
+///   (`async unordered(n)` caps the futures in flight, `async unordered indexed` yields `(index, value)`
+///   where `index` is the item's position in this step's input, or in the pipeline's input for
+///   `indexed` and `traced` pipelines)
+/// - `spawn async |x| { ... }` - Asynchronous operation with each item on its own tokio task, so
+///   CPU work between awaits spreads over the runtime's threads (`spawn(n) async` runs at most `n`
+///   tasks at once). The block must own what it uses, as with `tokio::spawn`; items whose task
+///   panics get a [`TaskError`](crate::error::TaskError)
+/// - `blocking |x| expr` - Run a blocking closure, such as file or database calls, on tokio's
+///   blocking pool so the async steps around it keep running (`blocking(n)` runs at most `n` at
+///   once). Like `spawn async`, the closure must own what it uses
 /// - `||| |x| expr` - Parallel processing (requires "parallel" feature)
+/// - `async ||| |x| expr` - Parallel processing for async pipelines: the stage runs on the rayon
+///   pool and is awaited, so the tokio worker thread stays free for other tasks. The closure must
+///   own what it uses, as with `rayon::spawn`
+/// - `retry(policy) |x| expr` - Re-run the closure on a clone of each failed item according to a
+///   [`RetryPolicy`](crate::retry::RetryPolicy); `async retry(policy) |x| { ... }` waits for backoff
+///   delays on tokio's clock. Items still failing on the last attempt end with a
+///   [`RetryError`](crate::retry::RetryError) recording the number of attempts; the policy's
+///   `on_finish` callback receives the attempts of every item
+/// - `tap |x| expr` - Run a side effect on each successful item (by reference) and pass it on
+///   untouched; `tap |x| expr, on_err |e| expr` also inspects errors
+/// - `recover |e| expr` - Give each error a second chance: the closure returns a `Result` for the
+///   item, `Ok` to replace the error with a value or `Err` to keep failing (`recover async |e| { ... }`)
+/// - `map_err |e| expr` - Rewrite each error, possibly into another type (`map_err async |e| { ... }`)
+/// - `keep |x| expr` - Pair each item with the closure's output as `(x, output)`; the closure borrows
+///   the item (`keep ||| |x|` and `keep async |x| { ... }` also exist)
+/// - `expand |x| expr` - Turn each item into zero or more items; the closure returns a `Vec`
+///   or a `Result` of any iterable (`expand ||| |x|` and `expand async |x| { ... }` also exist)
+/// - `batch(n)` - Group successful items into `Vec`s of `n`; errors are forwarded unchanged ahead
+///   of the batch being filled and never count toward its size. `unbatch` flattens batches again
+/// - `async batch(n) |xs| { ... }` - Call the closure once per batch of up to `n` items; it returns
+///   a `Vec` with one result per item (or, with `async batch(n) keyed`, a `HashMap` keyed by item,
+///   the closure then gets each distinct item once) and each result is routed back to the items it
+///   belongs to
+/// - `? |x| predicate` - Keep only successful items matching the predicate (`? ||| |x|` and
+///   `? async |x| { ... }` evaluate it in parallel or asynchronously); errors pass through
+/// - `catch |x| expr` - Like the sync step, but a panic in the closure becomes a
+///   [`PanicError`](crate::error::PanicError) for that item and the other items carry on
+///   (`catch ||| |x|` and `catch async |x| { ... }` also exist)
+/// - `"name": step` - Name the step that follows, for the errors of a traced pipeline
+///
+/// # Terminal steps
+///
+/// - `fold(init, |acc, x| expr)` - Fold successful values into `Result<Acc, E>`
+/// - `reduce(|a, b| expr)` - Combine successful values into `Result<Option<T>, E>`
+///   (`||| reduce(...)` uses rayon's parallel reduction; the closure must be associative)
+/// - `collect` / `collect::<C>` - Collect into any `FromIterator<Result<T, E>>` target, such as
+///   `Result<HashMap<K, V>, E>`, `Result<String, E>` or `Vec<Result<T, E>>`
+/// - `partition` - Split into `(Vec<T>, Vec<E>)`
+/// - `ok_or_errors` - `Ok(Vec<T>)` if nothing failed, otherwise `Err(Vec<E>)` with every error
+///
+/// `fold` and `reduce` stop at the first error that reaches them and return it; `||| reduce` also
+/// returns the first error in input order, after reducing the whole input. Errors that should not
+/// stop the fold are dropped earlier by the producing step's strategy (e.g. `IgnoreHandler`).
+///
+/// # Errors
+///
+/// All steps of a pipeline share one error type `E`, and errors returned by a closure reach
+/// the end unchanged, so a typed error enum or a `Box<dyn Error + Send + Sync>` can still be
+/// matched or downcast there. Use `map_err` to convert between error types along the way.
+/// Errors created by a step itself, such as a [`TimeoutError`](crate::time::TimeoutError),
+/// are converted with `From`; `String` supports all of them. A pipeline whose steps can never
+/// fail has nothing to infer `E` from, so its result needs a type annotation.
+///
+/// `pipex!(traced input => ...)` wraps every error in a [`PipelineError<E>`](crate::error::PipelineError)
+/// recording the step that produced it and the position of its item in `input`. The
+/// closures of `recover` and `map_err` then receive the error's cause, and errors keep
+/// their location as they pass through.
+///
+/// `pipex!(indexed input => ...)` instead keeps errors as they are and pairs every result
+/// with the position of its item in `input`, giving `Vec<(usize, Result<T, E>)>`. Positions
+/// survive parallel and GPU steps, reordering, expansion and items dropped by strategies.
+/// Items leaving `unbatch` get their own position back as long as their batch kept its length.
+/// Its results can be collected with `collect`; the other terminal steps expect plain results.
+///
+/// `pipex!(fail_fast input => ...)` traces errors the same way but stops at the first one,
+/// returning `Result<Vec<T>, PipelineError<E>>`. Steps stop starting work on further items,
+/// in-flight futures are dropped, rayon stops handing out items and later steps only see
+/// that error. Error strategies are ignored.
+/// `recover` steps are rejected, since a stopped pipeline cannot be resumed:
+///
+/// ```compile_fail
+/// use pipex::pipex;
+///
+/// let result = pipex!(
+///     fail_fast vec![1, 2, 3]
+///     => |x| if x == 2 { Err("bad".to_string()) } else { Ok(x) }
+///     => recover |_e| Ok(0)
+/// );
+/// ```
 #[macro_export]
 macro_rules! pipex {
+    // Traced entry point - errors become `PipelineError`s recording the step and input item
+    (traced $input:expr $(=> $($rest:tt)+)?) => {{
+        let initial_results = $input
+            .into_iter()
+            .enumerate()
+            .map(|(input_index, x)| $crate::traits::Traced {
+                trace: $crate::traits::Trace::new(input_index),
+                item: Ok(x),
+            })
+            .collect::<Vec<$crate::traits::Traced<Result<_, $crate::error::PipelineError<_>>>>>();
+        pipex!(@process initial_results $(=> $($rest)+)?)
+    }};
+
+    // Fail-fast entry point - stops at the first error, the result is `Result<Vec<T>, PipelineError<E>>`
+    (fail_fast $input:expr $(=> $($rest:tt)+)?) => {{
+        let initial_results = $input
+            .into_iter()
+            .enumerate()
+            .map(|(input_index, x)| $crate::traits::Traced {
+                trace: $crate::traits::Trace::new(input_index),
+                item: Ok(x),
+            })
+            .collect::<Vec<$crate::traits::Traced<
+                Result<_, $crate::error::PipelineError<_>>,
+                $crate::traits::FailFast,
+            >>>();
+        pipex!(@process initial_results $(=> $($rest)+)?)
+    }};
+
+    // Indexed entry point - every item keeps its input position, the result is `Vec<(usize, Result<T, E>)>`
+    (indexed $input:expr $(=> $($rest:tt)+)?) => {{
+        let initial_results = $input
+            .into_iter()
+            .enumerate()
+            .map(|(input_index, x)| $crate::traits::Indexed {
+                index: $crate::traits::InputIndex::new(input_index),
+                item: Ok(x),
+            })
+            .collect::<Vec<$crate::traits::Indexed<Result<_, _>>>>();
+        pipex!(@process initial_results $(=> $($rest)+)?)
+    }};
+
     // Entry point
     ($input:expr $(=> $($rest:tt)+)?) => {{
         let initial_results = $input
             .into_iter()
             .map(|x| Ok(x))
-            .collect::<Vec<Result<_, ()>>>();
+            .collect::<Vec<Result<_, _>>>();
         pipex!(@process initial_results $(=> $($rest)+)?)
     }};
 
-    // SYNC step - process all items (successful and errors) uniformly like async
-    (@process $input:expr => |$var:ident| $body:expr $(=> $($rest:tt)+)?) => {{
-        let sync_results = $input
+    // STAGE LABEL - name the next step in the `PipelineError`s of a traced pipeline
+    (@process $input:expr => $name:literal : $($rest:tt)+) => {{
+        let named_results = $input
+            .into_iter()
+            .map(|item| $crate::traits::SplitItem::name_stage(item, $name))
+            .collect::<Vec<_>>();
+        pipex!(@process named_results => $($rest)+)
+    }};
+
+    // SYNC step - process all items (successful and errors) uniformly like async
+    (@process $input:expr => |$var:ident| $body:expr $(=> $($rest:tt)+)?) => {{
+        let sync_results = $input
+            .into_iter()
+            .map(|item| {
+                let (tag, item_result) = $crate::traits::SplitItem::split_item(item);
+                match item_result {
+                    Ok($var) => {
+                        use $crate::traits::IntoPipelineItem;
+                        $crate::traits::JoinOutput::join_output(tag, ($body).into_pipeline_item())
+                    },
+                    Err(e) => {
+                        $crate::traits::ItemTag::retag(tag, <_ as $crate::CreateError<_>>::create_error(e))
+                    }
+                }
+            });
+        let sync_results = $crate::short_circuit::collect_items(sync_results);
+        
+        let iter_result = $crate::traits::ApplyStrategies::apply_strategies(sync_results);
+        pipex!(@process iter_result $(=> $($rest)+)?)
+    }};
+
+    // ASYNC step - process all items (successful and errors) uniformly
+    (@process $input:expr => async |$var:ident| $body:block $(=> $($rest:tt)+)?) => {{
+        let result = {
+            async {
+                #[cfg(feature = "async")]
+                {
+                    let futures_results = $crate::short_circuit::join_items(
+                        $input.into_iter().map(|item| async move {
+                            let (tag, item_result) = $crate::traits::SplitItem::split_item(item);
+                            match item_result {
+                                Ok($var) => {
+                                    $crate::traits::JoinOutput::join_output(tag, $body)
+                                },
+                                Err(e) => {
+                                    $crate::traits::ItemTag::retag(tag, <_ as $crate::CreateError<_>>::create_error(e))
+                                }
+                            }
+                        })
+                    ).await;
+                    
+                    $crate::traits::ApplyStrategies::apply_strategies(futures_results)
+                }
+                #[cfg(not(feature = "async"))]
+                {
+                    compile_error!("Async pipeline operations require the 'async' feature to be enabled");
+                }
+            }
+        };
+        pipex!(@process result.await $(=> $($rest)+)?)
+    }};
+
+    // ASYNC BOUNDED step - like the async step, but with at most `$limit` futures in flight
+    (@process $input:expr => async($limit:expr) |$var:ident| $body:block $(=> $($rest:tt)+)?) => {{
+        let result = {
+            async {
+                #[cfg(feature = "async")]
+                {
+                    let limit: usize = $limit;
+                    assert!(limit > 0, "async(N) step requires a concurrency limit of at least 1");
+                    let futures_results = $crate::short_circuit::buffer_items(
+                        $input.into_iter().map(|item| async move {
+                            let (tag, item_result) = $crate::traits::SplitItem::split_item(item);
+                            match item_result {
+                                Ok($var) => {
+                                    $crate::traits::JoinOutput::join_output(tag, $body)
+                                },
+                                Err(e) => {
+                                    $crate::traits::ItemTag::retag(tag, <_ as $crate::CreateError<_>>::create_error(e))
+                                }
+                            }
+                        }),
+                        limit,
+                    ).await;
+
+                    $crate::traits::ApplyStrategies::apply_strategies(futures_results)
+                }
+                #[cfg(not(feature = "async"))]
+                {
+                    compile_error!("Async pipeline operations require the 'async' feature to be enabled");
+                }
+            }
+        };
+        pipex!(@process result.await $(=> $($rest)+)?)
+    }};
+
+    // ASYNC TIMEOUT step - items still running after `$duration` become a `TimeoutError`
+    (@process $input:expr => async timeout($($duration:tt)+) |$var:ident| $body:block $(=> $($rest:tt)+)?) => {{
+        let result = {
+            async {
+                #[cfg(feature = "async")]
+                {
+                    let duration: std::time::Duration = pipex!(@duration $($duration)+);
+                    let futures_results = $crate::short_circuit::join_items(
+                        $input.into_iter().map(|item| async move {
+                            let (tag, item_result) = $crate::traits::SplitItem::split_item(item);
+                            match item_result {
+                                Ok($var) => {
+                                    let output = match $crate::tokio::time::timeout(duration, async move { $body }).await {
+                                        Ok(output) => output,
+                                        Err(_) => <_ as $crate::CreateError<_>>::create_error(
+                                            $crate::time::TimeoutError::new(duration).into()
+                                        ),
+                                    };
+                                    $crate::traits::JoinOutput::join_output(tag, output)
+                                },
+                                Err(e) => {
+                                    $crate::traits::ItemTag::retag(tag, <_ as $crate::CreateError<_>>::create_error(e))
+                                }
+                            }
+                        })
+                    ).await;
+
+                    $crate::traits::ApplyStrategies::apply_strategies(futures_results)
+                }
+                #[cfg(not(feature = "async"))]
+                {
+                    compile_error!("Async pipeline operations require the 'async' feature to be enabled");
+                }
+            }
+        };
+        pipex!(@process result.await $(=> $($rest)+)?)
+    }};
+
+    // ASYNC RATE step - literal rate such as `100/s`, see the limiter form below
+    (@process $input:expr => async rate($permits:literal / $unit:ident) |$var:ident| $body:block $(=> $($rest:tt)+)?) => {{
+        let period = match $crate::time::parse_duration(concat!("1", stringify!($unit))) {
+            Some(period) => period,
+            None => panic!("invalid rate unit `{}`, expected e.g. `100/s`, `5/ms` or `600/m`", stringify!($unit)),
+        };
+        let limiter = $crate::rate::RateLimiter::new($permits, period);
+        pipex!(@process $input => async rate(limiter) |$var| $body $(=> $($rest)+)?)
+    }};
+
+    // ASYNC RATE step - each item takes a permit from a `RateLimiter` before it starts
+    (@process $input:expr => async rate($limiter:expr) |$var:ident| $body:block $(=> $($rest:tt)+)?) => {{
+        let result = {
+            async {
+                #[cfg(feature = "async")]
+                {
+                    let limiter: &$crate::rate::RateLimiter = &$limiter;
+                    let futures_results = $crate::short_circuit::join_items(
+                        $input.into_iter().map(|item| async move {
+                            let (tag, item_result) = $crate::traits::SplitItem::split_item(item);
+                            match item_result {
+                                Ok($var) => {
+                                    limiter.acquire().await;
+                                    $crate::traits::JoinOutput::join_output(tag, $body)
+                                },
+                                Err(e) => {
+                                    $crate::traits::ItemTag::retag(tag, <_ as $crate::CreateError<_>>::create_error(e))
+                                }
+                            }
+                        })
+                    ).await;
+
+                    $crate::traits::ApplyStrategies::apply_strategies(futures_results)
+                }
+                #[cfg(not(feature = "async"))]
+                {
+                    compile_error!("Async pipeline operations require the 'async' feature to be enabled");
+                }
+            }
+        };
+        pipex!(@process result.await $(=> $($rest)+)?)
+    }};
+
+    // ASYNC UNORDERED step - emit results in completion order, optionally capped at `$limit` in flight
+    (@process $input:expr => async unordered $(($limit:expr))? |$var:ident| $body:block $(=> $($rest:tt)+)?) => {{
+        let result = {
+            async {
+                #[cfg(feature = "async")]
+                {
+                    use $crate::futures::stream::StreamExt;
+                    let limit: usize = None $(.or(Some($limit)))?.unwrap_or(usize::MAX);
+                    assert!(limit > 0, "async unordered(N) step requires a concurrency limit of at least 1");
+                    let futures_results = $crate::short_circuit::collect_stream_items(
+                        $crate::futures::stream::iter(
+                            $input.into_iter().map(|item| async move {
+                                let (tag, item_result) = $crate::traits::SplitItem::split_item(item);
+                                match item_result {
+                                    Ok($var) => {
+                                        $crate::traits::JoinOutput::join_output(tag, $body)
+                                    },
+                                    Err(e) => {
+                                        $crate::traits::ItemTag::retag(tag, <_ as $crate::CreateError<_>>::create_error(e))
+                                    }
+                                }
+                            })
+                        )
+                        .buffer_unordered(limit)
+                    ).await;
+
+                    $crate::traits::ApplyStrategies::apply_strategies(futures_results)
+                }
+                #[cfg(not(feature = "async"))]
+                {
+                    compile_error!("Async pipeline operations require the 'async' feature to be enabled");
+                }
+            }
+        };
+        pipex!(@process result.await $(=> $($rest)+)?)
+    }};
+
+    // ASYNC UNORDERED INDEXED step - completion order, successful values tagged with an index
+    //
+    // The index is the item's position in the pipeline's input for `indexed`, `traced` and
+    // `fail_fast` pipelines, and its position in this step's input otherwise.
+    (@process $input:expr => async unordered $(($limit:expr))? indexed |$var:ident| $body:block $(=> $($rest:tt)+)?) => {{
+        let result = {
+            async {
+                #[cfg(feature = "async")]
+                {
+                    use $crate::futures::stream::StreamExt;
+                    let limit: usize = None $(.or(Some($limit)))?.unwrap_or(usize::MAX);
+                    assert!(limit > 0, "async unordered(N) step requires a concurrency limit of at least 1");
+                    let futures_results = $crate::short_circuit::collect_stream_items(
+                        $crate::futures::stream::iter(
+                            $input.into_iter().enumerate().map(|(position, item)| async move {
+                                let (tag, item_result) = $crate::traits::SplitItem::split_item(item);
+                                match item_result {
+                                    Ok($var) => {
+                                        let output = $body;
+                                        use $crate::traits::TagIndex;
+                                        let index = $crate::traits::ItemTag::input_index(&tag).unwrap_or(position);
+                                        $crate::traits::JoinOutput::join_output(tag, output.tag_index(index))
+                                    },
+                                    Err(e) => {
+                                        $crate::traits::ItemTag::retag(tag, <_ as $crate::CreateError<_>>::create_error(e))
+                                    }
+                                }
+                            })
+                        )
+                        .buffer_unordered(limit)
+                    ).await;
+
+                    $crate::traits::ApplyStrategies::apply_strategies(futures_results)
+                }
+                #[cfg(not(feature = "async"))]
+                {
+                    compile_error!("Async pipeline operations require the 'async' feature to be enabled");
+                }
+            }
+        };
+        pipex!(@process result.await $(=> $($rest)+)?)
+    }};
+
+    // SPAWN step - run each item as a tokio task so async steps use every runtime thread
+    //
+    // At most `$limit` tasks run at once. An item whose task panics or is cancelled gets a
+    // `TaskError`, and tasks still running are aborted when the pipeline stops waiting for them.
+    (@process $input:expr => spawn $(($limit:expr))? async |$var:ident| $body:block $(=> $($rest:tt)+)?) => {{
+        let result = {
+            async {
+                #[cfg(feature = "async")]
+                {
+                    let limit: usize = None $(.or(Some($limit)))?.unwrap_or(usize::MAX);
+                    assert!(limit > 0, "spawn(N) async step requires a concurrency limit of at least 1");
+                    let futures_results = $crate::short_circuit::buffer_items(
+                        $input.into_iter().map(|item| async move {
+                            let (tag, item_result) = $crate::traits::SplitItem::split_item(item);
+                            match item_result {
+                                Ok($var) => {
+                                    let output = match $crate::task::spawn_item(async move $body).await {
+                                        Ok(output) => output,
+                                        Err(error) => <_ as $crate::CreateError<_>>::create_error(error.into()),
+                                    };
+                                    $crate::traits::JoinOutput::join_output(tag, output)
+                                },
+                                Err(e) => {
+                                    $crate::traits::ItemTag::retag(tag, <_ as $crate::CreateError<_>>::create_error(e))
+                                }
+                            }
+                        }),
+                        limit,
+                    ).await;
+
+                    $crate::traits::ApplyStrategies::apply_strategies(futures_results)
+                }
+                #[cfg(not(feature = "async"))]
+                {
+                    compile_error!("Async pipeline operations require the 'async' feature to be enabled");
+                }
+            }
+        };
+        pipex!(@process result.await $(=> $($rest)+)?)
+    }};
+
+    // BLOCKING step - run each item's closure on tokio's blocking pool, at most `$limit` at once
+    (@process $input:expr => blocking $(($limit:expr))? |$var:ident| $body:expr $(=> $($rest:tt)+)?) => {{
+        let result = {
+            async {
+                #[cfg(feature = "async")]
+                {
+                    let limit: usize = None $(.or(Some($limit)))?.unwrap_or(usize::MAX);
+                    assert!(limit > 0, "blocking(N) step requires a concurrency limit of at least 1");
+                    let futures_results = $crate::short_circuit::buffer_items(
+                        $input.into_iter().map(|item| async move {
+                            let (tag, item_result) = $crate::traits::SplitItem::split_item(item);
+                            match item_result {
+                                Ok($var) => {
+                                    let output = match $crate::task::spawn_blocking_item(move || {
+                                        use $crate::traits::IntoPipelineItem;
+                                        ($body).into_pipeline_item()
+                                    }).await {
+                                        Ok(output) => output,
+                                        Err(error) => <_ as $crate::CreateError<_>>::create_error(error.into()),
+                                    };
+                                    $crate::traits::JoinOutput::join_output(tag, output)
+                                },
+                                Err(e) => {
+                                    $crate::traits::ItemTag::retag(tag, <_ as $crate::CreateError<_>>::create_error(e))
+                                }
+                            }
+                        }),
+                        limit,
+                    ).await;
+
+                    $crate::traits::ApplyStrategies::apply_strategies(futures_results)
+                }
+                #[cfg(not(feature = "async"))]
+                {
+                    compile_error!("Async pipeline operations require the 'async' feature to be enabled");
+                }
+            }
+        };
+        pipex!(@process result.await $(=> $($rest)+)?)
+    }};
+
+    // ASYNC BATCH step - call `$body` once per batch of up to `$size` items (dataloader style)
+    //
+    // The closure receives a `Vec` of items and returns one result per item, in the same order.
+    (@process $input:expr => async batch($size:expr) |$var:ident| $body:block $(=> $($rest:tt)+)?) => {{
+        let result = {
+            async {
+                #[cfg(feature = "async")]
+                {
+                    let size: usize = $size;
+                    assert!(size > 0, "async batch(N) step requires a batch size of at least 1");
+
+                    // Group successful items into batches, remembering each item's position
+                    let mut slots = Vec::new();
+                    let mut tags = Vec::new();
+                    let mut batches: Vec<Vec<_>> = Vec::new();
+                    let mut batch_positions: Vec<Vec<usize>> = Vec::new();
+                    let mut forwarded_errors = Vec::new();
+                    for (position, item) in $input.into_iter().enumerate() {
+                        let (tag, item_result) = $crate::traits::SplitItem::split_item(item);
+                        match item_result {
+                            Ok(item) => {
+                                if batches.last().is_none_or(|batch| batch.len() == size) {
+                                    batches.push(Vec::with_capacity(size));
+                                    batch_positions.push(Vec::with_capacity(size));
+                                }
+                                batches.last_mut().unwrap().push(item);
+                                batch_positions.last_mut().unwrap().push(position);
+                            },
+                            Err(e) => forwarded_errors.push((position, e)),
+                        }
+                        tags.push(tag);
+                        slots.push(None);
+                    }
+
+                    // Each batch puts its results together with the tags of its items
+                    let batch_outputs = $crate::short_circuit::join_items(
+                        batches.into_iter().zip(batch_positions).map(|($var, positions)| {
+                            let batch_tags = positions
+                                .iter()
+                                .map(|&position| tags[position].clone())
+                                .collect::<Vec<_>>();
+                            async move {
+                                let keys = (0..$var.len()).collect::<Vec<usize>>();
+                                use $crate::traits::IntoScatteredItems;
+                                ($body)
+                                    .into_scattered_items(keys)
+                                    .into_iter()
+                                    .zip(positions.into_iter().zip(batch_tags))
+                                    .map(|(output, (position, tag))| {
+                                        (position, $crate::traits::JoinOutput::join_output(tag, output))
+                                    })
+                                    .collect::<Vec<_>>()
+                            }
+                        })
+                    ).await;
+
+                    // Scatter each batch's results back to the positions they came from
+                    for (position, item) in batch_outputs.into_iter().flatten() {
+                        slots[position] = Some(item);
+                    }
+                    for (position, error) in forwarded_errors {
+                        slots[position] = Some($crate::traits::ItemTag::retag(
+                            tags[position].clone(),
+                            $crate::CreateError::create_error(error),
+                        ));
+                    }
+                    // Only the batches a fail-fast pipeline stopped waiting for leave empty slots
+                    let batch_results = slots.into_iter().flatten().collect::<Vec<_>>();
+
+                    $crate::traits::ApplyStrategies::apply_strategies(batch_results)
+                }
+                #[cfg(not(feature = "async"))]
+                {
+                    compile_error!("Async pipeline operations require the 'async' feature to be enabled");
+                }
+            }
+        };
+        pipex!(@process result.await $(=> $($rest)+)?)
+    }};
+
+    // ASYNC KEYED BATCH step - like `async batch`, but the closure returns a map keyed by input item
+    (@process $input:expr => async batch($size:expr) keyed |$var:ident| $body:block $(=> $($rest:tt)+)?) => {{
+        let result = {
+            async {
+                #[cfg(feature = "async")]
+                {
+                    let size: usize = $size;
+                    assert!(size > 0, "async batch(N) step requires a batch size of at least 1");
+
+                    // Group successful items into batches, remembering each item's position
+                    let mut slots = Vec::new();
+                    let mut tags = Vec::new();
+                    let mut batches: Vec<Vec<_>> = Vec::new();
+                    let mut batch_positions: Vec<Vec<usize>> = Vec::new();
+                    let mut forwarded_errors = Vec::new();
+                    for (position, item) in $input.into_iter().enumerate() {
+                        let (tag, item_result) = $crate::traits::SplitItem::split_item(item);
+                        match item_result {
+                            Ok(item) => {
+                                if batches.last().is_none_or(|batch| batch.len() == size) {
+                                    batches.push(Vec::with_capacity(size));
+                                    batch_positions.push(Vec::with_capacity(size));
+                                }
+                                batches.last_mut().unwrap().push(item);
+                                batch_positions.last_mut().unwrap().push(position);
+                            },
+                            Err(e) => forwarded_errors.push((position, e)),
+                        }
+                        tags.push(tag);
+                        slots.push(None);
+                    }
+
+                    // Each batch puts its results together with the tags of its items
+                    let batch_outputs = $crate::short_circuit::join_items(
+                        batches.into_iter().zip(batch_positions).map(|($var, positions)| {
+                            let batch_tags = positions
+                                .iter()
+                                .map(|&position| tags[position].clone())
+                                .collect::<Vec<_>>();
+                            async move {
+                                // The closure sees every key once, items sharing a key share its result
+                                let keys = $var.clone();
+                                let $var = $crate::traits::unique_keys($var);
+                                use $crate::traits::IntoScatteredItems;
+                                ($body)
+                                    .into_scattered_items(keys)
+                                    .into_iter()
+                                    .zip(positions.into_iter().zip(batch_tags))
+                                    .map(|(output, (position, tag))| {
+                                        (position, $crate::traits::JoinOutput::join_output(tag, output))
+                                    })
+                                    .collect::<Vec<_>>()
+                            }
+                        })
+                    ).await;
+
+                    // Scatter each batch's results back to the positions they came from
+                    for (position, item) in batch_outputs.into_iter().flatten() {
+                        slots[position] = Some(item);
+                    }
+                    for (position, error) in forwarded_errors {
+                        slots[position] = Some($crate::traits::ItemTag::retag(
+                            tags[position].clone(),
+                            $crate::CreateError::create_error(error),
+                        ));
+                    }
+                    // Only the batches a fail-fast pipeline stopped waiting for leave empty slots
+                    let batch_results = slots.into_iter().flatten().collect::<Vec<_>>();
+
+                    $crate::traits::ApplyStrategies::apply_strategies(batch_results)
+                }
+                #[cfg(not(feature = "async"))]
+                {
+                    compile_error!("Async pipeline operations require the 'async' feature to be enabled");
+                }
+            }
+        };
+        pipex!(@process result.await $(=> $($rest)+)?)
+    }};
+
+    // PARALLEL step - process items in parallel with uniform error handling
+    (@process $input:expr => ||| |$var:ident| $body:expr $(=> $($rest:tt)+)?) => {{
+        let result = {
+            #[cfg(feature = "parallel")]
+            {
+                use $crate::rayon::prelude::*;
+                let parallel_results_intermediate = $crate::short_circuit::collect_par_items($input.into_par_iter().map(|item| {
+                    let (tag, item_result) = $crate::traits::SplitItem::split_item(item);
+                    match item_result {
+                        Ok($var) => {
+                            use $crate::traits::IntoPipelineItem;
+                            $crate::traits::JoinOutput::join_output(tag, ($body).into_pipeline_item())
+                        },
+                        Err(e) => {
+                            $crate::traits::ItemTag::retag(tag, <_ as $crate::CreateError<_>>::create_error(e))
+                        }
+                    }
+                }));
+                
+                $crate::traits::ApplyStrategies::apply_strategies(parallel_results_intermediate)
+            }
+            #[cfg(not(feature = "parallel"))]
+            {
+                compile_error!("Parallel pipeline operations require the 'parallel' feature to be enabled");
+            }
+        };
+        pipex!(@process result $(=> $($rest)+)?)
+    }};
+
+    // CATCH step - a panic in the closure becomes a `PanicError` for its item instead of unwinding
+    (@process $input:expr => catch |$var:ident| $body:expr $(=> $($rest:tt)+)?) => {{
+        pipex!(@process $input => |$var| {
+            match std::panic::catch_unwind(std::panic::AssertUnwindSafe(|| $body)) {
+                Ok(output) => output,
+                Err(payload) => <_ as $crate::CreateError<_>>::create_error(
+                    $crate::error::PanicError::from_payload(payload).into()
+                ),
+            }
+        } $(=> $($rest)+)?)
+    }};
+
+    // PARALLEL CATCH step
+    (@process $input:expr => catch ||| |$var:ident| $body:expr $(=> $($rest:tt)+)?) => {{
+        pipex!(@process $input => ||| |$var| {
+            match std::panic::catch_unwind(std::panic::AssertUnwindSafe(|| $body)) {
+                Ok(output) => output,
+                Err(payload) => <_ as $crate::CreateError<_>>::create_error(
+                    $crate::error::PanicError::from_payload(payload).into()
+                ),
+            }
+        } $(=> $($rest)+)?)
+    }};
+
+    // ASYNC CATCH step - also catches panics while the future is polled
+    (@process $input:expr => catch async |$var:ident| $body:block $(=> $($rest:tt)+)?) => {{
+        pipex!(@process $input => async |$var| {
+            use $crate::futures::FutureExt;
+            match std::panic::AssertUnwindSafe(async move $body).catch_unwind().await {
+                Ok(output) => output,
+                Err(payload) => <_ as $crate::CreateError<_>>::create_error(
+                    $crate::error::PanicError::from_payload(payload).into()
+                ),
+            }
+        } $(=> $($rest)+)?)
+    }};
+
+    // ASYNC PARALLEL step - run the parallel step as a rayon job and await it through a oneshot,
+    // so the tokio worker thread is not blocked while the stage runs
+    (@process $input:expr => async ||| |$var:ident| $body:expr $(=> $($rest:tt)+)?) => {{
+        let result = {
+            async {
+                #[cfg(all(feature = "async", feature = "parallel"))]
+                {
+                    let items = $input;
+                    let (sender, receiver) = $crate::futures::channel::oneshot::channel();
+                    $crate::rayon::spawn(move || {
+                        // A panic is sent back to the pipeline, rayon would abort on it otherwise
+                        let job_result = std::panic::catch_unwind(std::panic::AssertUnwindSafe(move || {
+                            use $crate::rayon::prelude::*;
+                            $crate::short_circuit::collect_par_items(items.into_par_iter().map(|item| {
+                                let (tag, item_result) = $crate::traits::SplitItem::split_item(item);
+                                match item_result {
+                                    Ok($var) => {
+                                        use $crate::traits::IntoPipelineItem;
+                                        $crate::traits::JoinOutput::join_output(tag, ($body).into_pipeline_item())
+                                    },
+                                    Err(e) => {
+                                        $crate::traits::ItemTag::retag(tag, <_ as $crate::CreateError<_>>::create_error(e))
+                                    }
+                                }
+                            }))
+                        }));
+                        let _ = sender.send(job_result);
+                    });
+                    let parallel_results_intermediate = match receiver.await.expect("rayon job sends its results") {
+                        Ok(parallel_results_intermediate) => parallel_results_intermediate,
+                        Err(payload) => std::panic::resume_unwind(payload),
+                    };
+
+                    $crate::traits::ApplyStrategies::apply_strategies(parallel_results_intermediate)
+                }
+                #[cfg(not(all(feature = "async", feature = "parallel")))]
+                {
+                    compile_error!("Async parallel pipeline operations require the 'async' and 'parallel' features to be enabled");
+                }
+            }
+        };
+        pipex!(@process result.await $(=> $($rest)+)?)
+    }};
+
+    // RETRY step - re-run the closure on a clone of each failed item according to a `RetryPolicy`
+    (@process $input:expr => retry($policy:expr) |$var:ident| $body:expr $(=> $($rest:tt)+)?) => {{
+        let policy: &$crate::retry::RetryPolicy<_> = &$policy;
+        let retry_results = $input
             .into_iter()
-            .map(|item_result| {
+            .map(|item| {
+                let (tag, item_result) = $crate::traits::SplitItem::split_item(item);
                 match item_result {
-                    Ok($var) => {
-                        use $crate::traits::IntoPipelineItem;
-                        ($body).into_pipeline_item()
+                    Ok(input) => {
+                        let output = policy.run(|| {
+                            let $var = input.clone();
+                            use $crate::traits::IntoPipelineItem;
+                            ($body).into_pipeline_item()
+                        });
+                        $crate::traits::JoinOutput::join_output(tag, output)
                     },
                     Err(e) => {
-                        let mut error_string = format!("{:?}", e);
-                        while error_string.starts_with("\"") && error_string.ends_with("\"") {
-                            error_string = error_string[1..error_string.len()-1].to_string();
-                        }
-                        <_ as $crate::CreateError<String>>::create_error(error_string)
+                        $crate::traits::ItemTag::retag(tag, <_ as $crate::CreateError<_>>::create_error(e))
                     }
                 }
+            });
+        let retry_results = $crate::short_circuit::collect_items(retry_results);
+
+        let retry_results = $crate::traits::ApplyStrategies::apply_strategies(retry_results);
+        pipex!(@process retry_results $(=> $($rest)+)?)
+    }};
+
+    // ASYNC RETRY step - like the retry step, waiting for backoff delays on tokio's clock
+    (@process $input:expr => async retry($policy:expr) |$var:ident| $body:block $(=> $($rest:tt)+)?) => {{
+        let result = {
+            async {
+                #[cfg(feature = "async")]
+                {
+                    let policy: &$crate::retry::RetryPolicy<_> = &$policy;
+                    let futures_results = $crate::short_circuit::join_items(
+                        $input.into_iter().map(|item| async move {
+                            let (tag, item_result) = $crate::traits::SplitItem::split_item(item);
+                            match item_result {
+                                Ok(input) => {
+                                    let output = policy.run_async(|| {
+                                        let $var = input.clone();
+                                        async move { $body }
+                                    }).await;
+                                    $crate::traits::JoinOutput::join_output(tag, output)
+                                },
+                                Err(e) => {
+                                    $crate::traits::ItemTag::retag(tag, <_ as $crate::CreateError<_>>::create_error(e))
+                                }
+                            }
+                        })
+                    ).await;
+
+                    $crate::traits::ApplyStrategies::apply_strategies(futures_results)
+                }
+                #[cfg(not(feature = "async"))]
+                {
+                    compile_error!("Async pipeline operations require the 'async' feature to be enabled");
+                }
+            }
+        };
+        pipex!(@process result.await $(=> $($rest)+)?)
+    }};
+
+    // FILTER step - drop successful items failing the predicate, errors pass through unchanged
+    (@process $input:expr => ? |$var:ident| $body:expr $(=> $($rest:tt)+)?) => {{
+        let filtered_results = $input
+            .into_iter()
+            .filter_map(|item| {
+                let (tag, item_result) = $crate::traits::SplitItem::split_item(item);
+                let keep = match &item_result {
+                    Ok($var) => $body,
+                    Err(_) => true,
+                };
+                keep.then(|| $crate::traits::ItemTag::retag(tag, item_result))
             })
             .collect::<Vec<_>>();
-        
-        use $crate::PipelineResultHandler;
-        let iter_result = sync_results.handle_pipeline_results();
-        pipex!(@process iter_result $(=> $($rest)+)?)
+        pipex!(@process filtered_results $(=> $($rest)+)?)
     }};
 
-    // ASYNC step - process all items (successful and errors) uniformly
-    (@process $input:expr => async |$var:ident| $body:block $(=> $($rest:tt)+)?) => {{
+    // PARALLEL FILTER step - evaluate the predicate in parallel with rayon
+    (@process $input:expr => ? ||| |$var:ident| $body:expr $(=> $($rest:tt)+)?) => {{
+        let result = {
+            #[cfg(feature = "parallel")]
+            {
+                use $crate::rayon::prelude::*;
+                $input.into_par_iter().filter_map(|item| {
+                    let (tag, item_result) = $crate::traits::SplitItem::split_item(item);
+                    let keep = match &item_result {
+                        Ok($var) => $body,
+                        Err(_) => true,
+                    };
+                    keep.then(|| $crate::traits::ItemTag::retag(tag, item_result))
+                }).collect::<Vec<_>>()
+            }
+            #[cfg(not(feature = "parallel"))]
+            {
+                compile_error!("Parallel pipeline operations require the 'parallel' feature to be enabled");
+            }
+        };
+        pipex!(@process result $(=> $($rest)+)?)
+    }};
+
+    // ASYNC FILTER step - evaluate an async predicate for all items concurrently
+    (@process $input:expr => ? async |$var:ident| $body:block $(=> $($rest:tt)+)?) => {{
         let result = {
             async {
                 #[cfg(feature = "async")]
                 {
-                    let futures_results = $crate::futures::future::join_all(
+                    let kept_items = $crate::short_circuit::join_items(
                         $input.into_iter().map(|item| async move {
-                            match item {
-                                Ok($var) => {
-                                    $body
+                            let (tag, item_result) = $crate::traits::SplitItem::split_item(item);
+                            let keep = match &item_result {
+                                Ok($var) => $body,
+                                Err(_) => true,
+                            };
+                            keep.then(|| $crate::traits::ItemTag::retag(tag, item_result))
+                        })
+                    ).await;
+
+                    kept_items.into_iter().flatten().collect::<Vec<_>>()
+                }
+                #[cfg(not(feature = "async"))]
+                {
+                    compile_error!("Async pipeline operations require the 'async' feature to be enabled");
+                }
+            }
+        };
+        pipex!(@process result.await $(=> $($rest)+)?)
+    }};
+
+    // TAP step - run side effects on items by reference, passing every item through untouched
+    (@process $input:expr => tap |$var:ident| $body:expr $(, on_err |$err:ident| $err_body:expr)? $(=> $($rest:tt)+)?) => {{
+        let tapped_results = $input
+            .into_iter()
+            .map(|item| {
+                let (tag, item_result) = $crate::traits::SplitItem::split_item(item);
+                match &item_result {
+                    Ok($var) => {
+                        $body;
+                    },
+                    Err(_error) => {
+                        $(
+                            let $err = _error;
+                            $err_body;
+                        )?
+                    }
+                }
+                $crate::traits::ItemTag::retag(tag, item_result)
+            })
+            .collect::<Vec<_>>();
+        pipex!(@process tapped_results $(=> $($rest)+)?)
+    }};
+
+    // RECOVER step - the closure gets each error and returns a `Result` for the item; successes pass through
+    (@process $input:expr => recover |$err:ident| $body:expr $(=> $($rest:tt)+)?) => {{
+        let recovered_results = $input
+            .into_iter()
+            .map(|item| {
+                let (tag, item_result) = $crate::traits::SplitItem::split_item(item);
+                let tag = $crate::traits::recoverable(tag);
+                match item_result {
+                    Ok(value) => $crate::traits::ItemTag::retag(tag, Ok(value)),
+                    Err(error) => {
+                        let ($err, context) = $crate::traits::SplitError::split_error(&tag, error);
+                        let output: Result<_, _> = $body;
+                        $crate::traits::ItemTag::retag(
+                            tag,
+                            output.map_err(|cause| $crate::traits::WrapCause::wrap_cause(context, cause)),
+                        )
+                    }
+                }
+            })
+            .collect::<Vec<_>>();
+        pipex!(@process recovered_results $(=> $($rest)+)?)
+    }};
+
+    // ASYNC RECOVER step
+    (@process $input:expr => recover async |$err:ident| $body:block $(=> $($rest:tt)+)?) => {{
+        let result = {
+            async {
+                #[cfg(feature = "async")]
+                {
+                    $crate::short_circuit::join_items(
+                        $input.into_iter().map(|item| async move {
+                            let (tag, item_result) = $crate::traits::SplitItem::split_item(item);
+                            let tag = $crate::traits::recoverable(tag);
+                            match item_result {
+                                Ok(value) => $crate::traits::ItemTag::retag(tag, Ok(value)),
+                                Err(error) => {
+                                    let ($err, context) = $crate::traits::SplitError::split_error(&tag, error);
+                                    let output: Result<_, _> = $body;
+                                    $crate::traits::ItemTag::retag(
+                                        tag,
+                                        output.map_err(|cause| $crate::traits::WrapCause::wrap_cause(context, cause)),
+                                    )
+                                }
+                            }
+                        })
+                    ).await
+                }
+                #[cfg(not(feature = "async"))]
+                {
+                    compile_error!("Async pipeline operations require the 'async' feature to be enabled");
+                }
+            }
+        };
+        pipex!(@process result.await $(=> $($rest)+)?)
+    }};
+
+    // MAP_ERR step - replace each error with the closure's result; successes pass through
+    (@process $input:expr => map_err |$err:ident| $body:expr $(=> $($rest:tt)+)?) => {{
+        let mapped_results = $input
+            .into_iter()
+            .map(|item| {
+                let (tag, item_result) = $crate::traits::SplitItem::split_item(item);
+                match item_result {
+                    Ok(value) => $crate::traits::ItemTag::retag(tag, Ok(value)),
+                    Err(error) => {
+                        let ($err, context) = $crate::traits::SplitError::split_error(&tag, error);
+                        $crate::traits::ItemTag::retag(tag, Err($crate::traits::WrapCause::wrap_cause(context, $body)))
+                    }
+                }
+            })
+            .collect::<Vec<_>>();
+        pipex!(@process mapped_results $(=> $($rest)+)?)
+    }};
+
+    // ASYNC MAP_ERR step
+    (@process $input:expr => map_err async |$err:ident| $body:block $(=> $($rest:tt)+)?) => {{
+        let result = {
+            async {
+                #[cfg(feature = "async")]
+                {
+                    $crate::short_circuit::join_items(
+                        $input.into_iter().map(|item| async move {
+                            let (tag, item_result) = $crate::traits::SplitItem::split_item(item);
+                            match item_result {
+                                Ok(value) => $crate::traits::ItemTag::retag(tag, Ok(value)),
+                                Err(error) => {
+                                    let ($err, context) = $crate::traits::SplitError::split_error(&tag, error);
+                                    $crate::traits::ItemTag::retag(tag, Err($crate::traits::WrapCause::wrap_cause(context, $body)))
+                                }
+                            }
+                        })
+                    ).await
+                }
+                #[cfg(not(feature = "async"))]
+                {
+                    compile_error!("Async pipeline operations require the 'async' feature to be enabled");
+                }
+            }
+        };
+        pipex!(@process result.await $(=> $($rest)+)?)
+    }};
+
+    // KEEP step - the closure borrows each item, which is paired with its output as `(input, output)`
+    (@process $input:expr => keep |$var:ident| $body:expr $(=> $($rest:tt)+)?) => {{
+        let kept_results = $input
+            .into_iter()
+            .map(|item| {
+                let (tag, item_result) = $crate::traits::SplitItem::split_item(item);
+                match item_result {
+                    Ok(input) => {
+                        use $crate::traits::IntoPipelineItem;
+                        let output = {
+                            let $var = &input;
+                            ($body).into_pipeline_item()
+                        };
+                        $crate::traits::JoinOutput::join_output(tag, $crate::traits::PairWithInput::pair_with_input(output, input))
+                    },
+                    Err(e) => {
+                        $crate::traits::ItemTag::retag(tag, <_ as $crate::CreateError<_>>::create_error(e))
+                    }
+                }
+            });
+        let kept_results = $crate::short_circuit::collect_items(kept_results);
+
+        let kept_results = $crate::traits::ApplyStrategies::apply_strategies(kept_results);
+        pipex!(@process kept_results $(=> $($rest)+)?)
+    }};
+
+    // PARALLEL KEEP step
+    (@process $input:expr => keep ||| |$var:ident| $body:expr $(=> $($rest:tt)+)?) => {{
+        let result = {
+            #[cfg(feature = "parallel")]
+            {
+                use $crate::rayon::prelude::*;
+                let parallel_results_intermediate = $crate::short_circuit::collect_par_items($input.into_par_iter().map(|item| {
+                    let (tag, item_result) = $crate::traits::SplitItem::split_item(item);
+                    match item_result {
+                        Ok(input) => {
+                            use $crate::traits::IntoPipelineItem;
+                            let output = {
+                                let $var = &input;
+                                ($body).into_pipeline_item()
+                            };
+                            $crate::traits::JoinOutput::join_output(tag, $crate::traits::PairWithInput::pair_with_input(output, input))
+                        },
+                        Err(e) => {
+                            $crate::traits::ItemTag::retag(tag, <_ as $crate::CreateError<_>>::create_error(e))
+                        }
+                    }
+                }));
+
+                $crate::traits::ApplyStrategies::apply_strategies(parallel_results_intermediate)
+            }
+            #[cfg(not(feature = "parallel"))]
+            {
+                compile_error!("Parallel pipeline operations require the 'parallel' feature to be enabled");
+            }
+        };
+        pipex!(@process result $(=> $($rest)+)?)
+    }};
+
+    // ASYNC KEEP step
+    (@process $input:expr => keep async |$var:ident| $body:block $(=> $($rest:tt)+)?) => {{
+        let result = {
+            async {
+                #[cfg(feature = "async")]
+                {
+                    let futures_results = $crate::short_circuit::join_items(
+                        $input.into_iter().map(|item| async move {
+                            let (tag, item_result) = $crate::traits::SplitItem::split_item(item);
+                            match item_result {
+                                Ok(input) => {
+                                    let output = {
+                                        let $var = &input;
+                                        $body
+                                    };
+                                    $crate::traits::JoinOutput::join_output(tag, $crate::traits::PairWithInput::pair_with_input(output, input))
                                 },
                                 Err(e) => {
-                                    let mut error_string = format!("{:?}", e);
-                                    while error_string.starts_with("\"") && error_string.ends_with("\"") {
-                                        error_string = error_string[1..error_string.len()-1].to_string();
-                                    }
-                                    <_ as $crate::CreateError<String>>::create_error(error_string)
+                                    $crate::traits::ItemTag::retag(tag, <_ as $crate::CreateError<_>>::create_error(e))
                                 }
                             }
                         })
                     ).await;
-                    
-                    use $crate::PipelineResultHandler;
-                    futures_results.handle_pipeline_results()
+
+                    $crate::traits::ApplyStrategies::apply_strategies(futures_results)
                 }
                 #[cfg(not(feature = "async"))]
                 {
@@ -82,30 +1118,48 @@ macro_rules! pipex {
         pipex!(@process result.await $(=> $($rest)+)?)
     }};
 
-        // PARALLEL step - process items in parallel with uniform error handling
-    (@process $input:expr => ||| |$var:ident| $body:expr $(=> $($rest:tt)+)?) => {{
+    // EXPAND step - turn each item into zero or more items (flat_map)
+    (@process $input:expr => expand |$var:ident| $body:expr $(=> $($rest:tt)+)?) => {{
+        let expand_results = $input
+            .into_iter()
+            .map(|item| {
+                let (tag, item_result) = $crate::traits::SplitItem::split_item(item);
+                match item_result {
+                    Ok($var) => {
+                        use $crate::traits::IntoExpandedItem;
+                        $crate::traits::JoinOutput::join_output(tag, ($body).into_expanded_item())
+                    },
+                    Err(e) => {
+                        $crate::traits::ItemTag::retag(tag, <_ as $crate::CreateError<_>>::create_error(e))
+                    }
+                }
+            });
+        let expand_results = $crate::short_circuit::collect_items(expand_results);
+
+        let iter_result = pipex!(@flatten $crate::traits::ApplyStrategies::apply_strategies(expand_results));
+        pipex!(@process iter_result $(=> $($rest)+)?)
+    }};
+
+    // PARALLEL EXPAND step - expand items in parallel, flattened in input order
+    (@process $input:expr => expand ||| |$var:ident| $body:expr $(=> $($rest:tt)+)?) => {{
         let result = {
             #[cfg(feature = "parallel")]
             {
                 use $crate::rayon::prelude::*;
-                let parallel_results_intermediate = $input.into_par_iter().map(|item_result| {
+                let parallel_results_intermediate = $crate::short_circuit::collect_par_items($input.into_par_iter().map(|item| {
+                    let (tag, item_result) = $crate::traits::SplitItem::split_item(item);
                     match item_result {
                         Ok($var) => {
-                            use $crate::traits::IntoPipelineItem;
-                            ($body).into_pipeline_item()
+                            use $crate::traits::IntoExpandedItem;
+                            $crate::traits::JoinOutput::join_output(tag, ($body).into_expanded_item())
                         },
                         Err(e) => {
-                            let mut error_string = format!("{:?}", e);
-                            while error_string.starts_with("\"") && error_string.ends_with("\"") {
-                                error_string = error_string[1..error_string.len()-1].to_string();
-                            }
-                            <_ as $crate::CreateError<String>>::create_error(error_string)
+                            $crate::traits::ItemTag::retag(tag, <_ as $crate::CreateError<_>>::create_error(e))
                         }
                     }
-                }).collect::<Vec<_>>();
-                
-                use $crate::PipelineResultHandler;
-                parallel_results_intermediate.handle_pipeline_results()
+                }));
+
+                pipex!(@flatten $crate::traits::ApplyStrategies::apply_strategies(parallel_results_intermediate))
             }
             #[cfg(not(feature = "parallel"))]
             {
@@ -115,6 +1169,78 @@ macro_rules! pipex {
         pipex!(@process result $(=> $($rest)+)?)
     }};
 
+    // ASYNC EXPAND step - expand all items concurrently, flattened in input order
+    (@process $input:expr => expand async |$var:ident| $body:block $(=> $($rest:tt)+)?) => {{
+        let result = {
+            async {
+                #[cfg(feature = "async")]
+                {
+                    let futures_results = $crate::short_circuit::join_items(
+                        $input.into_iter().map(|item| async move {
+                            let (tag, item_result) = $crate::traits::SplitItem::split_item(item);
+                            match item_result {
+                                Ok($var) => {
+                                    use $crate::traits::IntoExpandedItem;
+                                    $crate::traits::JoinOutput::join_output(tag, ($body).into_expanded_item())
+                                },
+                                Err(e) => {
+                                    $crate::traits::ItemTag::retag(tag, <_ as $crate::CreateError<_>>::create_error(e))
+                                }
+                            }
+                        })
+                    ).await;
+
+                    pipex!(@flatten $crate::traits::ApplyStrategies::apply_strategies(futures_results))
+                }
+                #[cfg(not(feature = "async"))]
+                {
+                    compile_error!("Async pipeline operations require the 'async' feature to be enabled");
+                }
+            }
+        };
+        pipex!(@process result.await $(=> $($rest)+)?)
+    }};
+
+    // BATCH step - group successful items into `Vec`s of `$size`
+    //
+    // Errors never enter a batch and do not count toward its size: each one is forwarded
+    // unchanged at the point it arrives, ahead of the batch still being filled. The last
+    // batch may be shorter than `$size`.
+    (@process $input:expr => batch($size:expr) $(=> $($rest:tt)+)?) => {{
+        let size: usize = $size;
+        assert!(size > 0, "batch(N) step requires a batch size of at least 1");
+        let mut batched_results = Vec::new();
+        let mut current_batch = Vec::with_capacity(size);
+        // The batch keeps the tag of every item in it, so `unbatch` can give them back
+        let mut current_tags = Vec::with_capacity(size);
+        for item in $input {
+            let (tag, item_result) = $crate::traits::SplitItem::split_item(item);
+            match item_result {
+                Ok(item) => {
+                    current_batch.push(item);
+                    current_tags.push(tag);
+                    if current_batch.len() == size {
+                        let batch = std::mem::replace(&mut current_batch, Vec::with_capacity(size));
+                        let tags = std::mem::replace(&mut current_tags, Vec::with_capacity(size));
+                        let batch_tag = $crate::traits::ItemTag::batch_tag(tags);
+                        batched_results.push($crate::traits::ItemTag::retag(batch_tag, Ok(batch)));
+                    }
+                },
+                Err(e) => batched_results.push($crate::traits::ItemTag::retag(tag, Err(e))),
+            }
+        }
+        if !current_batch.is_empty() {
+            let batch_tag = $crate::traits::ItemTag::batch_tag(current_tags);
+            batched_results.push($crate::traits::ItemTag::retag(batch_tag, Ok(current_batch)));
+        }
+        pipex!(@process batched_results $(=> $($rest)+)?)
+    }};
+
+    // UNBATCH step - flatten batches back into one item per value, errors pass through
+    (@process $input:expr => unbatch $(=> $($rest:tt)+)?) => {{
+        pipex!(@process $input => expand |batch| batch $(=> $($rest)+)?)
+    }};
+
     // GPU AUTO step - automatic Rust-to-WGSL transpilation 
     (@process $input:expr => gpu ||| |$var:ident| $body:expr $(=> $($rest:tt)+)?) => {{
         let result = {
@@ -126,15 +1252,16 @@ macro_rules! pipex {
                     let mut input_items = Vec::new();
                     let mut success_indices = Vec::new();
                     
-                    for (idx, item_result) in $input.into_iter().enumerate() {
+                    for (idx, item) in $input.into_iter().enumerate() {
+                        let (tag, item_result) = $crate::traits::SplitItem::split_item(item);
                         match item_result {
                             Ok(item) => {
                                 gpu_inputs.push(item);
                                 success_indices.push(idx);
-                                input_items.push(Ok(()));  // Placeholder for successful items
+                                input_items.push((tag, Ok(())));  // Placeholder for successful items
                             },
                             Err(e) => {
-                                input_items.push(Err(e)); // Preserve errors
+                                input_items.push((tag, Err(e))); // Preserve errors
                             }
                         }
                     }
@@ -161,14 +1288,14 @@ macro_rules! pipex {
                     
                     // Map results back to their original positions
                     let mut gpu_idx = 0;
-                    input_items.into_iter().map(|item_result| {
+                    input_items.into_iter().map(|(tag, item_result)| {
                         match item_result {
                             Ok(_) => {
-                                let result = Ok(gpu_results[gpu_idx].clone());
+                                let result = $crate::traits::JoinOutput::join_output(tag, Ok(gpu_results[gpu_idx].clone()));
                                 gpu_idx += 1;
                                 result
                             },
-                            Err(e) => Err(format!("{:?}", e)), // Convert error to String
+                            Err(e) => $crate::traits::ItemTag::retag(tag, Err(e)), // Preserve errors
                         }
                     }).collect::<Vec<_>>()
                 }
@@ -177,10 +1304,11 @@ macro_rules! pipex {
             {
                 // Fallback to CPU parallel processing when GPU not available
                 use $crate::rayon::prelude::*;
-                $input.into_par_iter().map(|item_result| {
+                $input.into_par_iter().map(|item| {
+                    let (tag, item_result) = $crate::traits::SplitItem::split_item(item);
                     match item_result {
-                        Ok($var) => Ok($body),
-                        Err(e) => Err(format!("{:?}", e)),
+                        Ok($var) => $crate::traits::JoinOutput::join_output(tag, Ok($body)),
+                        Err(e) => $crate::traits::ItemTag::retag(tag, Err(e)),
                     }
                 }).collect::<Vec<_>>()
             }
@@ -199,15 +1327,16 @@ macro_rules! pipex {
                     let mut input_items = Vec::new();
                     let mut success_indices = Vec::new();
                     
-                    for (idx, item_result) in $input.into_iter().enumerate() {
+                    for (idx, item) in $input.into_iter().enumerate() {
+                        let (tag, item_result) = $crate::traits::SplitItem::split_item(item);
                         match item_result {
                             Ok(item) => {
                                 gpu_inputs.push(item);
                                 success_indices.push(idx);
-                                input_items.push(Ok(()));  // Placeholder for successful items
+                                input_items.push((tag, Ok(())));  // Placeholder for successful items
                             },
                             Err(e) => {
-                                input_items.push(Err(e)); // Preserve errors
+                                input_items.push((tag, Err(e))); // Preserve errors
                             }
                         }
                     }
@@ -218,11 +1347,10 @@ macro_rules! pipex {
                             Ok(results) => results,
                             Err(gpu_error) => {
                                 // If GPU fails, return error for all successful input positions
-                                let error_msg = format!("GPU execution failed: {}", gpu_error);
-                                return input_items.into_iter().map(|item_result| {
+                                return input_items.into_iter().map(|(tag, item_result)| {
                                     match item_result {
-                                        Ok(_) => Err(error_msg.clone()),
-                                        Err(e) => Err(format!("{:?}", e)), // Convert error to String
+                                        Ok(_) => $crate::traits::JoinOutput::join_output(tag, Err(gpu_error.clone().into())),
+                                        Err(e) => $crate::traits::ItemTag::retag(tag, Err(e)), // Preserve errors
                                     }
                                 }).collect::<Vec<_>>();
                             }
@@ -233,14 +1361,14 @@ macro_rules! pipex {
                     
                     // Map GPU results back to their original positions
                     let mut gpu_idx = 0;
-                    input_items.into_iter().map(|item_result| {
+                    input_items.into_iter().map(|(tag, item_result)| {
                         match item_result {
                             Ok(_) => {
-                                let result = Ok(gpu_results[gpu_idx].clone());
+                                let result = $crate::traits::JoinOutput::join_output(tag, Ok(gpu_results[gpu_idx].clone()));
                                 gpu_idx += 1;
                                 result
                             },
-                            Err(e) => Err(format!("{:?}", e)), // Convert error to String
+                            Err(e) => $crate::traits::ItemTag::retag(tag, Err(e)), // Preserve errors
                         }
                     }).collect::<Vec<_>>()
                 }
@@ -253,9 +1381,101 @@ macro_rules! pipex {
         pipex!(@process result.await $(=> $($rest)+)?)
     }};
 
+    // FOLD terminal - fold successful values into an accumulator, the first error stops the fold
+    (@process $input:expr => fold($init:expr, |$acc:ident, $var:ident| $body:expr)) => {{
+        $input
+            .into_iter()
+            .map($crate::traits::SplitItem::finish_item)
+            .try_fold($init, |$acc, item_result| {
+                match item_result {
+                    Ok($var) => Ok($body),
+                    Err(e) => Err(e),
+                }
+            })
+    }};
+
+    // REDUCE terminal - combine successful values pairwise, `Ok(None)` for an empty input
+    (@process $input:expr => reduce(|$a:ident, $b:ident| $body:expr)) => {{
+        let mut items = $input.into_iter().map($crate::traits::SplitItem::finish_item);
+        match items.next() {
+            None => Ok(None),
+            Some(Err(e)) => Err(e),
+            Some(Ok(first)) => items
+                .try_fold(first, |$a, item_result| {
+                    match item_result {
+                        Ok($b) => Ok($body),
+                        Err(e) => Err(e),
+                    }
+                })
+                .map(Some),
+        }
+    }};
+
+    // PARALLEL REDUCE terminal - rayon reduction, the closure must be associative. Rayon keeps the
+    // operands in input order, so keeping the left error returns the first one, like `reduce`
+    (@process $input:expr => ||| reduce(|$a:ident, $b:ident| $body:expr)) => {{
+        #[cfg(feature = "parallel")]
+        {
+            use $crate::rayon::prelude::*;
+            $input
+                .into_par_iter()
+                .map($crate::traits::SplitItem::finish_item)
+                .reduce_with(|left, right| match (left, right) {
+                    (Ok($a), Ok($b)) => Ok($body),
+                    (Err(e), _) | (Ok(_), Err(e)) => Err(e),
+                })
+                .transpose()
+        }
+        #[cfg(not(feature = "parallel"))]
+        {
+            compile_error!("Parallel pipeline operations require the 'parallel' feature to be enabled");
+        }
+    }};
+
+    // COLLECT terminal - collect the items into any `FromIterator<Result<T, E>>` target
+    (@process $input:expr => collect::<$target:ty>) => {{
+        $input.into_iter().map($crate::traits::SplitItem::finish_item).collect::<$target>()
+    }};
+
+    // COLLECT terminal - target type inferred from the surrounding code
+    (@process $input:expr => collect) => {{
+        $input.into_iter().map($crate::traits::SplitItem::finish_item).collect()
+    }};
+
+    // PARTITION terminal - `(Vec<T>, Vec<E>)`
+    (@process $input:expr => partition) => {{
+        use $crate::PartitionResults;
+        $input.into_iter().map($crate::traits::SplitItem::finish_item).collect::<Vec<_>>().partition_results()
+    }};
+
+    // OK OR ERRORS terminal - `Ok(Vec<T>)` without errors, otherwise `Err(Vec<E>)` with all of them
+    (@process $input:expr => ok_or_errors) => {{
+        use $crate::PartitionResults;
+        $input.into_iter().map($crate::traits::SplitItem::finish_item).collect::<Vec<_>>().ok_or_errors()
+    }};
+
     // Terminal case
     (@process $input:expr) => {{
-        $input.into_iter().collect::<Vec<_>>()
+        $crate::traits::FinishItems::finish_items($input)
+    }};
+
+    // Internal: flatten expanded `Result<Vec<T>, E>` items into one `Result<T, E>` per value
+    (@flatten $results:expr) => {{
+        $results
+            .into_iter()
+            .flat_map($crate::traits::FlattenItem::flatten_item)
+            .collect::<Vec<_>>()
+    }};
+
+    // Internal: a duration argument, either a literal such as `500ms` or a `Duration` expression
+    (@duration $duration:literal) => {{
+        match $crate::time::parse_duration(stringify!($duration)) {
+            Some(duration) => duration,
+            None => panic!("invalid duration literal `{}`, expected e.g. `500ms`, `30s` or `2m`", stringify!($duration)),
+        }
+    }};
+    (@duration $duration:expr) => {{
+        $duration
     }};
 }
 
@@ -0,0 +1,273 @@
+//! Streaming pipeline macro implementation
+
+/// Lazy streaming pipeline macro
+///
+/// `pipex_stream!` accepts the same step syntax as [`pipex!`](crate::pipex) but never
+/// collects a stage: it returns an `impl Stream<Item = Result<T, E>>` in which every
+/// item flows through all steps on its own, so unbounded inputs run in bounded memory.
+///
+/// The input is any `Stream`, or any `IntoIterator` when prefixed with `iter`.
+/// Error handling strategies are applied to each item as it passes, so strategies that
+/// look at a whole stage at once (such as a custom "first error only" handler) see one
+/// item at a time.
+///
+/// Prefixing the input with `pipelined(n)` runs every step as its own tokio task, with
+/// steps connected by channels holding at most `n` items. A slow step then overlaps with
+/// the steps around it: the next step starts on the first item as soon as it is produced,
+/// and a full channel makes the steps before it wait. In this mode step closures are
+/// `move` closures and, like everything sent between tasks, must be `Send + 'static`.
+///
+/// # Syntax
+///
+/// - `|x| expr` - Synchronous transformation
+/// - `async |x| { ... }` - Asynchronous operation, one item at a time
+/// - `async(n) |x| { ... }` - Asynchronous operation with at most `n` futures in flight
+/// - `||| |x| expr` - Parallel processing of the items that are ready as a rayon job, which the
+///   stream awaits. The closure must own what it uses, as with `rayon::spawn`
+/// - `? |x| predicate` - Keep only successful items matching the predicate
+/// - `tap |x| expr` - Side effect on each successful item (optionally `, on_err |e| expr`)
+///
+/// # Examples
+///
+/// ```rust
+/// use pipex::pipex_stream;
+/// use pipex::futures::StreamExt;
+///
+/// # tokio_test::block_on(async {
+/// let first_squares = pipex_stream!(
+///     iter 1u64..
+///     => |x| Ok::<u64, String>(x * x)
+///     => ? |x| x % 2 == 1
+/// )
+/// .take(3)
+/// .collect::<Vec<_>>()
+/// .await;
+///
+/// assert_eq!(first_squares, vec![Ok(1), Ok(9), Ok(25)]);
+/// # });
+/// ```
+#[macro_export]
+macro_rules! pipex_stream {
+    // Pipelined entry point for any IntoIterator input
+    (pipelined($capacity:expr) iter $input:expr $(=> $($rest:tt)+)?) => {{
+        #[cfg(feature = "async")]
+        {
+            pipex_stream!(pipelined($capacity) $crate::futures::stream::iter($input) $(=> $($rest)+)?)
+        }
+        #[cfg(not(feature = "async"))]
+        {
+            compile_error!("Streaming pipelines require the 'async' feature to be enabled");
+        }
+    }};
+
+    // Pipelined entry point for any Stream input - every step runs as its own task
+    (pipelined($capacity:expr) $input:expr $(=> $($rest:tt)+)?) => {{
+        #[cfg(feature = "async")]
+        {
+            use $crate::futures::StreamExt;
+            let capacity: usize = $capacity;
+            let initial_stream = $input.map(Ok);
+            pipex_stream!(@stage [move] [capacity] initial_stream $(=> $($rest)+)?)
+        }
+        #[cfg(not(feature = "async"))]
+        {
+            compile_error!("Streaming pipelines require the 'async' feature to be enabled");
+        }
+    }};
+
+    // Entry point for any IntoIterator input
+    (iter $input:expr $(=> $($rest:tt)+)?) => {{
+        #[cfg(feature = "async")]
+        {
+            pipex_stream!($crate::futures::stream::iter($input) $(=> $($rest)+)?)
+        }
+        #[cfg(not(feature = "async"))]
+        {
+            compile_error!("Streaming pipelines require the 'async' feature to be enabled");
+        }
+    }};
+
+    // Entry point for any Stream input
+    ($input:expr $(=> $($rest:tt)+)?) => {{
+        #[cfg(feature = "async")]
+        {
+            use $crate::futures::StreamExt;
+            let initial_stream = $input.map(Ok);
+            pipex_stream!(@stage [] [] initial_stream $(=> $($rest)+)?)
+        }
+        #[cfg(not(feature = "async"))]
+        {
+            compile_error!("Streaming pipelines require the 'async' feature to be enabled");
+        }
+    }};
+
+    // SYNC step
+    (@stage [$($move:tt)?] [$($capacity:expr)?] $stream:expr => |$var:ident| $body:expr $(=> $($rest:tt)+)?) => {{
+        use $crate::futures::StreamExt;
+        let sync_stream = $stream
+            .map($($move)? |item_result| {
+                match item_result {
+                    Ok($var) => {
+                        use $crate::traits::IntoPipelineItem;
+                        ($body).into_pipeline_item()
+                    },
+                    Err(e) => {
+                        <_ as $crate::CreateError<_>>::create_error(e)
+                    }
+                }
+            });
+        let sync_stream = pipex_stream!(@strategy sync_stream);
+        let sync_stream = pipex_stream!(@spawn [$($capacity)?] sync_stream);
+        pipex_stream!(@stage [$($move)?] [$($capacity)?] sync_stream $(=> $($rest)+)?)
+    }};
+
+    // ASYNC step - one item at a time
+    (@stage [$($move:tt)?] [$($capacity:expr)?] $stream:expr => async |$var:ident| $body:block $(=> $($rest:tt)+)?) => {{
+        use $crate::futures::StreamExt;
+        let async_stream = $stream
+            .then($($move)? |item| async move {
+                match item {
+                    Ok($var) => {
+                        $body
+                    },
+                    Err(e) => {
+                        <_ as $crate::CreateError<_>>::create_error(e)
+                    }
+                }
+            });
+        let async_stream = pipex_stream!(@strategy async_stream);
+        let async_stream = pipex_stream!(@spawn [$($capacity)?] async_stream);
+        pipex_stream!(@stage [$($move)?] [$($capacity)?] async_stream $(=> $($rest)+)?)
+    }};
+
+    // ASYNC BOUNDED step - at most `$limit` futures in flight, input order preserved
+    (@stage [$($move:tt)?] [$($capacity:expr)?] $stream:expr => async($limit:expr) |$var:ident| $body:block $(=> $($rest:tt)+)?) => {{
+        use $crate::futures::StreamExt;
+        let limit: usize = $limit;
+        assert!(limit > 0, "async(N) step requires a concurrency limit of at least 1");
+        let async_stream = $stream
+            .map($($move)? |item| async move {
+                match item {
+                    Ok($var) => {
+                        $body
+                    },
+                    Err(e) => {
+                        <_ as $crate::CreateError<_>>::create_error(e)
+                    }
+                }
+            })
+            .buffered(limit);
+        let async_stream = pipex_stream!(@strategy async_stream);
+        let async_stream = pipex_stream!(@spawn [$($capacity)?] async_stream);
+        pipex_stream!(@stage [$($move)?] [$($capacity)?] async_stream $(=> $($rest)+)?)
+    }};
+
+    // PARALLEL step - process the items that are ready together as a rayon job, awaited through a
+    // oneshot so the task polling the stream is not blocked while the chunk runs
+    (@stage [$($move:tt)?] [$($capacity:expr)?] $stream:expr => ||| |$var:ident| $body:expr $(=> $($rest:tt)+)?) => {{
+        #[cfg(feature = "parallel")]
+        {
+            use $crate::futures::StreamExt;
+            // Shared with every chunk's rayon job
+            let process_item = std::sync::Arc::new(move |item_result: Result<_, _>| {
+                match item_result {
+                    Ok($var) => {
+                        use $crate::traits::IntoPipelineItem;
+                        ($body).into_pipeline_item()
+                    },
+                    Err(e) => {
+                        <_ as $crate::CreateError<_>>::create_error(e)
+                    }
+                }
+            });
+            let parallel_stream = $stream
+                .ready_chunks($crate::rayon::current_num_threads())
+                .then(move |chunk| {
+                    let process_item = process_item.clone();
+                    let (sender, receiver) = $crate::futures::channel::oneshot::channel();
+                    $crate::rayon::spawn(move || {
+                        // A panic is sent back to the stream, rayon would abort on it otherwise
+                        let chunk_results = std::panic::catch_unwind(std::panic::AssertUnwindSafe(move || {
+                            use $crate::rayon::prelude::*;
+                            chunk.into_par_iter().map(|item_result| process_item(item_result)).collect::<Vec<_>>()
+                        }));
+                        let _ = sender.send(chunk_results);
+                    });
+                    async move {
+                        match receiver.await.expect("rayon job sends its results") {
+                            Ok(chunk_results) => $crate::futures::stream::iter(chunk_results),
+                            Err(payload) => std::panic::resume_unwind(payload),
+                        }
+                    }
+                })
+                .flatten();
+            let parallel_stream = pipex_stream!(@strategy parallel_stream);
+            let parallel_stream = pipex_stream!(@spawn [$($capacity)?] parallel_stream);
+            pipex_stream!(@stage [$($move)?] [$($capacity)?] parallel_stream $(=> $($rest)+)?)
+        }
+        #[cfg(not(feature = "parallel"))]
+        {
+            compile_error!("Parallel pipeline operations require the 'parallel' feature to be enabled");
+        }
+    }};
+
+    // FILTER step - drop successful items failing the predicate, errors pass through unchanged
+    (@stage [$($move:tt)?] [$($capacity:expr)?] $stream:expr => ? |$var:ident| $body:expr $(=> $($rest:tt)+)?) => {{
+        use $crate::futures::StreamExt;
+        let filtered_stream = $stream
+            .filter($($move)? |item_result| {
+                let keep = match item_result {
+                    Ok($var) => $body,
+                    Err(_) => true,
+                };
+                $crate::futures::future::ready(keep)
+            });
+        let filtered_stream = pipex_stream!(@spawn [$($capacity)?] filtered_stream);
+        pipex_stream!(@stage [$($move)?] [$($capacity)?] filtered_stream $(=> $($rest)+)?)
+    }};
+
+    // TAP step - run side effects on items by reference, passing every item through untouched
+    (@stage [$($move:tt)?] [$($capacity:expr)?] $stream:expr => tap |$var:ident| $body:expr $(, on_err |$err:ident| $err_body:expr)? $(=> $($rest:tt)+)?) => {{
+        use $crate::futures::StreamExt;
+        let tapped_stream = $stream
+            .inspect($($move)? |item_result| {
+                match item_result {
+                    Ok($var) => {
+                        $body;
+                    },
+                    Err(_error) => {
+                        $(
+                            let $err = _error;
+                            $err_body;
+                        )?
+                    }
+                }
+            });
+        let tapped_stream = pipex_stream!(@spawn [$($capacity)?] tapped_stream);
+        pipex_stream!(@stage [$($move)?] [$($capacity)?] tapped_stream $(=> $($rest)+)?)
+    }};
+
+    // Terminal case - hand the lazy stream back to the caller
+    (@stage [$($move:tt)?] [$($capacity:expr)?] $stream:expr) => {{
+        $stream
+    }};
+
+    // Internal: in pipelined mode, run the step on its own task behind a bounded channel
+    (@spawn [] $stream:expr) => {{
+        $stream
+    }};
+    (@spawn [$capacity:expr] $stream:expr) => {{
+        $crate::stream::spawn_stage($stream, $capacity)
+    }};
+
+    // Internal: apply each item's error handling strategy as it passes
+    (@strategy $stream:expr) => {{
+        use $crate::futures::StreamExt;
+        $stream
+            .map(|pipeline_item| {
+                use $crate::PipelineResultHandler;
+                $crate::futures::stream::iter(vec![pipeline_item].handle_pipeline_results())
+            })
+            .flatten()
+    }};
+}
@@ -0,0 +1,134 @@
+//! Rate limiting for asynchronous pipeline steps
+//!
+//! Requires the "async" feature and a tokio runtime.
+
+use std::collections::HashMap;
+use std::sync::{Arc, Mutex, OnceLock};
+use std::time::Duration;
+
+use tokio::time::Instant;
+
+// Limiters shared by name across pipelines
+static NAMED_LIMITERS: OnceLock<Mutex<HashMap<String, RateLimiter>>> = OnceLock::new();
+
+#[derive(Debug)]
+struct Bucket {
+    // May go negative: permits handed out ahead of time that callers are still waiting for
+    tokens: f64,
+    updated: Instant,
+}
+
+#[derive(Debug)]
+struct Inner {
+    burst: f64,
+    interval: Duration,
+    bucket: Mutex<Bucket>,
+}
+
+/// Token bucket limiting how often permits are handed out
+///
+/// `RateLimiter::new(100, Duration::from_secs(1))` hands out 100 permits per second,
+/// evenly spaced. Clones share the same bucket, so one limiter passed to several
+/// `async rate(limiter)` steps caps their combined throughput. Waiting uses tokio's
+/// clock and is served in the order callers arrive.
+///
+/// # Examples
+///
+/// ```rust
+/// use pipex::rate::RateLimiter;
+/// use std::time::Duration;
+///
+/// # tokio_test::block_on(async {
+/// let limiter = RateLimiter::new(1000, Duration::from_secs(1)).burst(2);
+/// limiter.acquire().await;
+/// limiter.acquire().await; // both served from the initial burst
+/// # });
+/// ```
+#[derive(Debug, Clone)]
+pub struct RateLimiter {
+    inner: Arc<Inner>,
+}
+
+impl RateLimiter {
+    /// Allow `permits` acquisitions per `per`, with no bursts beyond a single permit
+    ///
+    /// # Panics
+    ///
+    /// Panics if `permits` is zero or `per` is zero.
+    pub fn new(permits: u32, per: Duration) -> Self {
+        assert!(permits > 0, "a rate limiter requires at least 1 permit per period");
+        assert!(!per.is_zero(), "a rate limiter requires a non-zero period");
+        Self::with_burst(per / permits, 1)
+    }
+
+    /// Get the limiter registered under `name`, registering `limiter` if there is none yet
+    ///
+    /// Pipelines that use the same name share one bucket, and the limiter registered
+    /// first is the one that applies.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use pipex::rate::RateLimiter;
+    /// use std::time::Duration;
+    ///
+    /// let github = RateLimiter::named("github", RateLimiter::new(10, Duration::from_secs(1)).burst(5));
+    /// let same_bucket = RateLimiter::named("github", RateLimiter::new(1, Duration::from_secs(1)));
+    /// ```
+    pub fn named(name: &str, limiter: RateLimiter) -> Self {
+        let registry = NAMED_LIMITERS.get_or_init(|| Mutex::new(HashMap::new()));
+        let mut registry = registry.lock().unwrap();
+        registry
+            .entry(name.to_string())
+            .or_insert(limiter)
+            .clone()
+    }
+
+    /// Allow up to `burst` permits to be handed out at once after a quiet period
+    ///
+    /// The long-run rate is unchanged. The bucket starts full. This returns a new
+    /// limiter, so set it before cloning or registering the limiter.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `burst` is zero.
+    pub fn burst(self, burst: u32) -> Self {
+        assert!(burst > 0, "a rate limiter requires a burst of at least 1");
+        Self::with_burst(self.inner.interval, burst)
+    }
+
+    /// Wait until a permit is available and take it
+    pub async fn acquire(&self) {
+        let wait = {
+            let mut bucket = self.inner.bucket.lock().unwrap();
+            let now = Instant::now();
+            let refilled = now.duration_since(bucket.updated).as_secs_f64()
+                / self.inner.interval.as_secs_f64();
+            bucket.tokens = (bucket.tokens + refilled).min(self.inner.burst) - 1.0;
+            bucket.updated = now;
+
+            if bucket.tokens < 0.0 {
+                self.inner.interval.mul_f64(-bucket.tokens)
+            } else {
+                Duration::ZERO
+            }
+        };
+
+        if !wait.is_zero() {
+            tokio::time::sleep(wait).await;
+        }
+    }
+
+    fn with_burst(interval: Duration, burst: u32) -> Self {
+        Self {
+            inner: Arc::new(Inner {
+                burst: f64::from(burst),
+                interval,
+                bucket: Mutex::new(Bucket {
+                    tokens: f64::from(burst),
+                    updated: Instant::now(),
+                }),
+            }),
+        }
+    }
+}
@@ -0,0 +1,328 @@
+//! Retry policies for pipeline steps
+
+use std::fmt;
+use std::sync::Arc;
+use std::time::Duration;
+
+use crate::traits::RetryOutcome;
+
+/// Error returned by a retry step once an item has run out of attempts
+///
+/// It records how many times the closure ran for the item. Errors rejected by the policy's
+/// `retry_if` predicate are not wrapped: they come out of the step as the closure returned them.
+/// Pipelines whose error type is `String` receive it through its `Display` form,
+/// `"{error} (after {n} attempts)"`. Pipelines with their own error type implement
+/// `From<RetryError<E>>` for it.
+///
+/// # Examples
+///
+/// ```rust
+/// use pipex::retry::RetryError;
+///
+/// let error = RetryError { attempts: 3, error: "connection reset".to_string() };
+/// assert_eq!(String::from(error), "connection reset (after 3 attempts)");
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct RetryError<E> {
+    /// How many times the closure ran before giving up
+    pub attempts: usize,
+    /// The error returned by the last attempt
+    pub error: E,
+}
+
+impl<E: fmt::Display> fmt::Display for RetryError<E> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let plural = if self.attempts == 1 { "" } else { "s" };
+        write!(f, "{} (after {} attempt{})", self.error, self.attempts, plural)
+    }
+}
+
+impl<E: fmt::Debug + fmt::Display> std::error::Error for RetryError<E> {}
+
+impl From<RetryError<String>> for String {
+    fn from(error: RetryError<String>) -> Self {
+        error.to_string()
+    }
+}
+
+/// Predicate deciding whether an error is worth another attempt
+type RetryPredicate<E> = Arc<dyn Fn(&E) -> bool + Send + Sync>;
+
+/// Callback receiving the number of attempts and the final error, if any, of each item
+type FinishCallback<E> = Arc<dyn Fn(usize, Option<&E>) + Send + Sync>;
+
+#[derive(Debug, Clone, Copy)]
+enum Backoff {
+    None,
+    Fixed(Duration),
+    Exponential(Duration),
+}
+
+// What a retry loop does after an attempt
+enum Next {
+    Retry(Duration),
+    // Succeeded, or failed with an error the policy does not retry
+    Done,
+    // Failed with a retryable error on the last allowed attempt
+    Exhausted,
+}
+
+/// How a retry step re-runs failed items
+///
+/// A policy allows up to `max_attempts` runs of the closure per item, waits between
+/// attempts according to its backoff (capped by `max_delay` if set), and only retries errors
+/// accepted by its `retry_if` predicate (all errors by default). An `on_finish` callback learns
+/// how many attempts every item took, whether it ended up succeeding or failing.
+///
+/// # Examples
+///
+/// ```rust
+/// use pipex::retry::RetryPolicy;
+/// use std::time::Duration;
+///
+/// let policy = RetryPolicy::<String>::new(4)
+///     .exponential_backoff(Duration::from_millis(100))
+///     .max_delay(Duration::from_millis(250))
+///     .retry_if(|error| error.contains("timeout"));
+///
+/// assert_eq!(policy.delay_for(1), Duration::from_millis(100));
+/// assert_eq!(policy.delay_for(2), Duration::from_millis(200));
+/// assert_eq!(policy.delay_for(3), Duration::from_millis(250));
+///
+/// // The cap applies whatever order the builder methods are called in
+/// let policy = RetryPolicy::<String>::new(4)
+///     .max_delay(Duration::from_secs(1))
+///     .fixed_backoff(Duration::from_secs(5));
+/// assert_eq!(policy.delay_for(1), Duration::from_secs(1));
+/// ```
+pub struct RetryPolicy<E = String> {
+    max_attempts: usize,
+    backoff: Backoff,
+    max_delay: Option<Duration>,
+    jitter: bool,
+    retry_if: Option<RetryPredicate<E>>,
+    on_finish: Option<FinishCallback<E>>,
+}
+
+impl<E> Clone for RetryPolicy<E> {
+    fn clone(&self) -> Self {
+        Self {
+            max_attempts: self.max_attempts,
+            backoff: self.backoff,
+            max_delay: self.max_delay,
+            jitter: self.jitter,
+            retry_if: self.retry_if.clone(),
+            on_finish: self.on_finish.clone(),
+        }
+    }
+}
+
+impl<E> fmt::Debug for RetryPolicy<E> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("RetryPolicy")
+            .field("max_attempts", &self.max_attempts)
+            .field("backoff", &self.backoff)
+            .field("max_delay", &self.max_delay)
+            .field("jitter", &self.jitter)
+            .field("retry_if", &self.retry_if.is_some())
+            .field("on_finish", &self.on_finish.is_some())
+            .finish()
+    }
+}
+
+impl<E> RetryPolicy<E> {
+    /// Allow up to `max_attempts` runs per item, retrying immediately
+    ///
+    /// # Panics
+    ///
+    /// Panics if `max_attempts` is zero.
+    pub fn new(max_attempts: usize) -> Self {
+        assert!(max_attempts > 0, "a retry policy requires at least 1 attempt");
+        Self {
+            max_attempts,
+            backoff: Backoff::None,
+            max_delay: None,
+            jitter: false,
+            retry_if: None,
+            on_finish: None,
+        }
+    }
+
+    /// Wait the same `delay` before every retry
+    pub fn fixed_backoff(mut self, delay: Duration) -> Self {
+        self.backoff = Backoff::Fixed(delay);
+        self
+    }
+
+    /// Wait `initial` before the first retry and double the wait for each one after it
+    pub fn exponential_backoff(mut self, initial: Duration) -> Self {
+        self.backoff = Backoff::Exponential(initial);
+        self
+    }
+
+    /// Never wait longer than `max` between attempts, whichever backoff is used
+    pub fn max_delay(mut self, max: Duration) -> Self {
+        self.max_delay = Some(max);
+        self
+    }
+
+    /// Wait a random time between half and all of each backoff delay
+    ///
+    /// This keeps items that failed together from retrying in lockstep.
+    pub fn jitter(mut self) -> Self {
+        self.jitter = true;
+        self
+    }
+
+    /// Only retry errors for which `predicate` returns `true`; others fail straight away
+    pub fn retry_if<F>(mut self, predicate: F) -> Self
+    where
+        F: Fn(&E) -> bool + Send + Sync + 'static,
+    {
+        self.retry_if = Some(Arc::new(predicate));
+        self
+    }
+
+    /// Call `callback` once per item when it stops being retried, with the number of attempts
+    /// it took and its final error (`None` if it succeeded)
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use pipex::retry::RetryPolicy;
+    /// use std::sync::{Arc, Mutex};
+    ///
+    /// let attempts = Arc::new(Mutex::new(Vec::new()));
+    /// let recorded = attempts.clone();
+    /// let policy = RetryPolicy::<String>::new(3)
+    ///     .on_finish(move |attempts, error| recorded.lock().unwrap().push((attempts, error.is_some())));
+    ///
+    /// let mut calls = 0;
+    /// let result = policy.run(|| {
+    ///     calls += 1;
+    ///     if calls < 2 { Err("flaky".to_string()) } else { Ok(calls) }
+    /// });
+    ///
+    /// assert_eq!(result, Ok(2));
+    /// assert_eq!(*attempts.lock().unwrap(), vec![(2, false)]);
+    /// ```
+    pub fn on_finish<F>(mut self, callback: F) -> Self
+    where
+        F: Fn(usize, Option<&E>) + Send + Sync + 'static,
+    {
+        self.on_finish = Some(Arc::new(callback));
+        self
+    }
+
+    /// Maximum number of runs per item
+    pub fn max_attempts(&self) -> usize {
+        self.max_attempts
+    }
+
+    /// The backoff delay after failed attempt number `attempt` (starting at 1), before jitter
+    pub fn delay_for(&self, attempt: usize) -> Duration {
+        let delay = match self.backoff {
+            Backoff::None => Duration::ZERO,
+            Backoff::Fixed(delay) => delay,
+            Backoff::Exponential(initial) => {
+                let exponent = u32::try_from(attempt.saturating_sub(1)).unwrap_or(u32::MAX);
+                2u32.checked_pow(exponent)
+                    .and_then(|factor| initial.checked_mul(factor))
+                    .unwrap_or(Duration::MAX)
+            }
+        };
+        self.max_delay.map_or(delay, |max| delay.min(max))
+    }
+
+    /// Run `operation` until it succeeds, fails with an error that should not be retried,
+    /// or runs out of attempts, sleeping the current thread between attempts
+    ///
+    /// An error still failing on the last attempt is wrapped in a [`RetryError`] recording the
+    /// number of attempts. Errors rejected by `retry_if` are returned unchanged. Either way,
+    /// `on_finish` is told the number of attempts.
+    pub fn run<R, F>(&self, mut operation: F) -> R
+    where
+        R: RetryOutcome<E>,
+        F: FnMut() -> R,
+    {
+        let mut attempt = 1;
+        loop {
+            let outcome = operation();
+            match self.next(&outcome, attempt) {
+                Next::Retry(delay) => {
+                    if !delay.is_zero() {
+                        std::thread::sleep(delay);
+                    }
+                    attempt += 1;
+                }
+                Next::Done => return self.finish(outcome, attempt),
+                Next::Exhausted => return self.finish(outcome, attempt).into_exhausted(attempt),
+            }
+        }
+    }
+
+    /// Like [`run`](Self::run), but for asynchronous operations, waiting on tokio's clock
+    #[cfg(feature = "async")]
+    #[cfg_attr(docsrs, doc(cfg(feature = "async")))]
+    pub async fn run_async<R, F, Fut>(&self, mut operation: F) -> R
+    where
+        R: RetryOutcome<E>,
+        F: FnMut() -> Fut,
+        Fut: std::future::Future<Output = R>,
+    {
+        let mut attempt = 1;
+        loop {
+            let outcome = operation().await;
+            match self.next(&outcome, attempt) {
+                Next::Retry(delay) => {
+                    if !delay.is_zero() {
+                        tokio::time::sleep(delay).await;
+                    }
+                    attempt += 1;
+                }
+                Next::Done => return self.finish(outcome, attempt),
+                Next::Exhausted => return self.finish(outcome, attempt).into_exhausted(attempt),
+            }
+        }
+    }
+
+    // Report the final outcome of an item to `on_finish`
+    fn finish<R: RetryOutcome<E>>(&self, outcome: R, attempts: usize) -> R {
+        if let Some(on_finish) = &self.on_finish {
+            on_finish(attempts, outcome.retry_error());
+        }
+        outcome
+    }
+
+    // Whether to retry after attempt number `attempt` produced `outcome`, and how long to wait
+    fn next<R: RetryOutcome<E>>(&self, outcome: &R, attempt: usize) -> Next {
+        let Some(error) = outcome.retry_error() else {
+            return Next::Done;
+        };
+        if let Some(retry_if) = &self.retry_if
+            && !retry_if(error)
+        {
+            return Next::Done;
+        }
+        if attempt >= self.max_attempts {
+            return Next::Exhausted;
+        }
+
+        let delay = self.delay_for(attempt);
+        if self.jitter {
+            Next::Retry(delay.mul_f64(0.5 + random_fraction() / 2.0))
+        } else {
+            Next::Retry(delay)
+        }
+    }
+}
+
+// A random number in `[0, 1)` for jitter, which needs no more than "different on every call".
+// std seeds `RandomState`'s SipHash keys randomly per thread and changes them for every new
+// `RandomState`, so hashing a constant with a new one gives an unpredictable 64-bit value without
+// pulling in an RNG crate. Its top 53 bits fill the mantissa of an `f64`.
+fn random_fraction() -> f64 {
+    use std::hash::BuildHasher;
+    let random = std::collections::hash_map::RandomState::new().hash_one(0u8);
+    (random >> 11) as f64 / (1u64 << 53) as f64
+}
@@ -0,0 +1,197 @@
+//! Early exit for `fail_fast` pipelines, used by the pipeline macros
+//!
+//! Steps collect their items through these helpers. Items of other pipelines are
+//! collected as usual, while in a fail-fast pipeline collection stops at the first
+//! error and drops the work that has not finished yet.
+
+use crate::PipexResult;
+use crate::traits::{FailFast, Indexed, RunToEnd, Traced};
+
+pub trait ShortCircuit {
+    const SHORT_CIRCUITS: bool = false;
+
+    fn stops_pipeline(&self) -> bool {
+        false
+    }
+}
+
+impl<T, E> ShortCircuit for Result<T, E> {}
+
+impl<T, E> ShortCircuit for PipexResult<T, E> {}
+
+impl<R> ShortCircuit for Indexed<R> {}
+
+impl<R> ShortCircuit for Traced<R, RunToEnd> {}
+
+impl<T, E> ShortCircuit for Traced<Result<T, E>, FailFast> {
+    const SHORT_CIRCUITS: bool = true;
+
+    fn stops_pipeline(&self) -> bool {
+        self.item.is_err()
+    }
+}
+
+impl<T, E> ShortCircuit for Traced<PipexResult<T, E>, FailFast> {
+    const SHORT_CIRCUITS: bool = true;
+
+    fn stops_pipeline(&self) -> bool {
+        self.item.result.is_err()
+    }
+}
+
+/// A filter result, which stops the pipeline if the item was kept and is an error
+impl<I: ShortCircuit> ShortCircuit for Option<I> {
+    const SHORT_CIRCUITS: bool = I::SHORT_CIRCUITS;
+
+    fn stops_pipeline(&self) -> bool {
+        self.as_ref().is_some_and(ShortCircuit::stops_pipeline)
+    }
+}
+
+/// An item with its position in a step's input
+impl<I: ShortCircuit> ShortCircuit for (usize, I) {
+    const SHORT_CIRCUITS: bool = I::SHORT_CIRCUITS;
+
+    fn stops_pipeline(&self) -> bool {
+        self.1.stops_pipeline()
+    }
+}
+
+/// The items of a batch call, which stop the pipeline if any of them does
+impl<I: ShortCircuit> ShortCircuit for Vec<I> {
+    const SHORT_CIRCUITS: bool = I::SHORT_CIRCUITS;
+
+    fn stops_pipeline(&self) -> bool {
+        self.iter().any(ShortCircuit::stops_pipeline)
+    }
+}
+
+/// Collect `items`, or only the first error of a fail-fast pipeline
+pub fn collect_items<I>(items: I) -> Vec<I::Item>
+where
+    I: Iterator,
+    I::Item: ShortCircuit,
+{
+    if !I::Item::SHORT_CIRCUITS {
+        return items.collect();
+    }
+
+    let mut collected = Vec::new();
+    for item in items {
+        if item.stops_pipeline() {
+            return vec![item];
+        }
+        collected.push(item);
+    }
+    collected
+}
+
+/// Run `futures` concurrently and collect their items in order, like `join_all`
+///
+/// In a fail-fast pipeline the first error to complete is returned on its own and
+/// the futures still running are dropped.
+#[cfg(feature = "async")]
+pub async fn join_items<I>(futures: I) -> Vec<<I::Item as std::future::Future>::Output>
+where
+    I: IntoIterator,
+    I::Item: std::future::Future,
+    <I::Item as std::future::Future>::Output: ShortCircuit,
+{
+    if !<<I::Item as std::future::Future>::Output as ShortCircuit>::SHORT_CIRCUITS {
+        return futures::future::join_all(futures).await;
+    }
+    first_error_or_all(futures, usize::MAX).await
+}
+
+/// Like [`join_items`], with at most `limit` futures in flight
+#[cfg(feature = "async")]
+pub async fn buffer_items<I>(futures: I, limit: usize) -> Vec<<I::Item as std::future::Future>::Output>
+where
+    I: IntoIterator,
+    I::Item: std::future::Future,
+    <I::Item as std::future::Future>::Output: ShortCircuit,
+{
+    use futures::stream::StreamExt;
+
+    if !<<I::Item as std::future::Future>::Output as ShortCircuit>::SHORT_CIRCUITS {
+        return futures::stream::iter(futures).buffered(limit).collect().await;
+    }
+    first_error_or_all(futures, limit).await
+}
+
+#[cfg(feature = "async")]
+async fn first_error_or_all<I>(futures: I, limit: usize) -> Vec<<I::Item as std::future::Future>::Output>
+where
+    I: IntoIterator,
+    I::Item: std::future::Future,
+    <I::Item as std::future::Future>::Output: ShortCircuit,
+{
+    use futures::stream::StreamExt;
+
+    // Take items as they complete so an error is seen without waiting for earlier items
+    let completed = futures::stream::iter(
+        futures
+            .into_iter()
+            .enumerate()
+            .map(|(position, future)| async move { (position, future.await) }),
+    )
+    .buffer_unordered(limit);
+    let mut completed = std::pin::pin!(completed);
+    let mut finished = Vec::new();
+    while let Some((position, item)) = completed.next().await {
+        if item.stops_pipeline() {
+            return vec![item];
+        }
+        finished.push((position, item));
+    }
+
+    finished.sort_unstable_by_key(|(position, _)| *position);
+    finished.into_iter().map(|(_, item)| item).collect()
+}
+
+/// Collect the items of `stream`, or only the first error of a fail-fast pipeline
+#[cfg(feature = "async")]
+pub async fn collect_stream_items<S>(stream: S) -> Vec<S::Item>
+where
+    S: futures::stream::Stream,
+    S::Item: ShortCircuit,
+{
+    use futures::stream::StreamExt;
+
+    if !S::Item::SHORT_CIRCUITS {
+        return stream.collect().await;
+    }
+
+    let mut stream = std::pin::pin!(stream);
+    let mut collected = Vec::new();
+    while let Some(item) = stream.next().await {
+        if item.stops_pipeline() {
+            return vec![item];
+        }
+        collected.push(item);
+    }
+    collected
+}
+
+/// Collect a rayon iterator in order, or only the first error of a fail-fast pipeline
+#[cfg(feature = "parallel")]
+pub fn collect_par_items<I>(items: I) -> Vec<I::Item>
+where
+    I: rayon::iter::IndexedParallelIterator,
+    I::Item: ShortCircuit,
+{
+    use rayon::iter::ParallelIterator;
+
+    if !I::Item::SHORT_CIRCUITS {
+        return items.collect();
+    }
+
+    // Rayon stops handing out work once an `Err` has been produced
+    let collected = items
+        .map(|item| if item.stops_pipeline() { Err(item) } else { Ok(item) })
+        .collect::<Result<Vec<_>, _>>();
+    match collected {
+        Ok(collected) => collected,
+        Err(item) => vec![item],
+    }
+}
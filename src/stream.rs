@@ -0,0 +1,50 @@
+//! Helpers for streaming pipelines
+//!
+//! These are used by [`pipex_stream!`](crate::pipex_stream) and are also usable on
+//! their own. Requires the "async" feature and a tokio runtime.
+
+use futures::{Stream, StreamExt};
+
+/// Drive a stream on its own tokio task, handing its items over through a bounded channel
+///
+/// The returned stream yields the same items in the same order. At most `capacity` items
+/// wait in the channel; once it is full the task stops polling `stream`, which applies
+/// backpressure to everything upstream. Dropping the returned stream stops the task.
+///
+/// # Panics
+///
+/// Panics if `capacity` is zero or if called outside of a tokio runtime.
+///
+/// # Examples
+///
+/// ```rust
+/// use pipex::stream::spawn_stage;
+/// use pipex::futures::{stream, StreamExt};
+///
+/// # tokio_test::block_on(async {
+/// let doubled = spawn_stage(stream::iter(1..=3).map(|x| x * 2), 1);
+/// assert_eq!(doubled.collect::<Vec<_>>().await, vec![2, 4, 6]);
+/// # });
+/// ```
+pub fn spawn_stage<S>(stream: S, capacity: usize) -> impl Stream<Item = S::Item> + Send + 'static
+where
+    S: Stream + Send + 'static,
+    S::Item: Send + 'static,
+{
+    assert!(capacity > 0, "pipelined stages require a channel capacity of at least 1");
+    let (sender, receiver) = tokio::sync::mpsc::channel(capacity);
+
+    tokio::spawn(async move {
+        let mut stream = std::pin::pin!(stream);
+        while let Some(item) = stream.next().await {
+            if sender.send(item).await.is_err() {
+                // Downstream was dropped, nobody wants the rest
+                break;
+            }
+        }
+    });
+
+    futures::stream::unfold(receiver, |mut receiver| async move {
+        receiver.recv().await.map(|item| (item, receiver))
+    })
+}
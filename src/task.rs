@@ -0,0 +1,45 @@
+//! Tokio tasks for the items of `spawn` and `blocking` steps, used by the pipeline macros
+
+use std::future::Future;
+use std::pin::Pin;
+use std::task::{Context, Poll};
+
+use tokio::task::JoinHandle;
+
+use crate::error::TaskError;
+
+/// Run `future` as a tokio task that is aborted if the pipeline stops waiting for it
+pub fn spawn_item<F>(future: F) -> SpawnedItem<F::Output>
+where
+    F: Future + Send + 'static,
+    F::Output: Send + 'static,
+{
+    SpawnedItem(tokio::spawn(future))
+}
+
+/// Run `f` on tokio's blocking pool, it is not started if the pipeline stops waiting first
+pub fn spawn_blocking_item<F, T>(f: F) -> SpawnedItem<T>
+where
+    F: FnOnce() -> T + Send + 'static,
+    T: Send + 'static,
+{
+    SpawnedItem(tokio::task::spawn_blocking(f))
+}
+
+/// Output of an item's task, see [`spawn_item`] and [`spawn_blocking_item`]
+pub struct SpawnedItem<T>(JoinHandle<T>);
+
+impl<T> Future for SpawnedItem<T> {
+    type Output = Result<T, TaskError>;
+
+    fn poll(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Self::Output> {
+        Pin::new(&mut self.0).poll(cx).map(|joined| joined.map_err(TaskError::from))
+    }
+}
+
+impl<T> Drop for SpawnedItem<T> {
+    fn drop(&mut self) {
+        // No effect once the task has finished
+        self.0.abort();
+    }
+}
@@ -0,0 +1,80 @@
+//! Time-related helpers for pipeline steps
+
+use std::fmt;
+use std::time::Duration;
+
+/// Error produced when an item exceeds the deadline of an `async timeout(...)` step
+///
+/// Pipelines whose error type is `String` receive it through its `Display` form,
+/// `"timed out after {duration:?}"`. Pipelines with their own error type can implement
+/// `From<TimeoutError>` for it to match timeouts directly.
+///
+/// # Examples
+///
+/// ```rust
+/// use pipex::time::TimeoutError;
+/// use std::time::Duration;
+///
+/// let error = TimeoutError::new(Duration::from_millis(500));
+/// assert_eq!(error.to_string(), "timed out after 500ms");
+/// assert_eq!(String::from(error), "timed out after 500ms");
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct TimeoutError {
+    /// The deadline that was exceeded
+    pub duration: Duration,
+}
+
+impl TimeoutError {
+    /// Create a timeout error for the given deadline
+    pub fn new(duration: Duration) -> Self {
+        Self { duration }
+    }
+}
+
+impl fmt::Display for TimeoutError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "timed out after {:?}", self.duration)
+    }
+}
+
+impl std::error::Error for TimeoutError {}
+
+impl From<TimeoutError> for String {
+    fn from(error: TimeoutError) -> Self {
+        error.to_string()
+    }
+}
+
+/// Parse a duration literal such as `500ms`, `30s` or `2m`
+///
+/// The value is a whole number followed by one of the units `ns`, `us`, `ms`, `s`,
+/// `m` or `h`. This is what the pipeline macros use for duration arguments written
+/// as literals.
+///
+/// # Examples
+///
+/// ```rust
+/// use pipex::time::parse_duration;
+/// use std::time::Duration;
+///
+/// assert_eq!(parse_duration("500ms"), Some(Duration::from_millis(500)));
+/// assert_eq!(parse_duration("2m"), Some(Duration::from_secs(120)));
+/// assert_eq!(parse_duration("500"), None);
+/// ```
+pub fn parse_duration(literal: &str) -> Option<Duration> {
+    let literal = literal.trim();
+    let unit_start = literal.find(|c: char| !c.is_ascii_digit())?;
+    let (value, unit) = literal.split_at(unit_start);
+    let value: u64 = value.parse().ok()?;
+
+    match unit {
+        "ns" => Some(Duration::from_nanos(value)),
+        "us" => Some(Duration::from_micros(value)),
+        "ms" => Some(Duration::from_millis(value)),
+        "s" => Some(Duration::from_secs(value)),
+        "m" => Some(Duration::from_secs(value.checked_mul(60)?)),
+        "h" => Some(Duration::from_secs(value.checked_mul(3600)?)),
+        _ => None,
+    }
+}
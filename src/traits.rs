@@ -1,6 +1,11 @@
 //! Core traits for pipeline functionality
 
+use std::collections::{HashMap, VecDeque};
+use std::marker::PhantomData;
+use std::sync::Arc;
+
 use crate::PipexResult;
+use crate::error::{BatchError, PipelineError};
 
 /// Trait to handle pipeline results uniformly
 /// 
@@ -34,6 +39,47 @@ pub trait ExtractSuccessful<T> {
     fn extract_successful(self) -> Vec<T>;
 }
 
+/// Trait to extract error values from pipeline results
+/// 
+/// This is the counterpart of [`ExtractSuccessful`]: it keeps only the
+/// errors from a collection of results, discarding successful values.
+/// 
+/// # Examples
+/// 
+/// ```rust
+/// use pipex::ExtractErrors;
+/// 
+/// let results: Vec<Result<i32, &str>> = vec![Ok(1), Err("error"), Ok(3)];
+/// let errors: Vec<&str> = results.extract_errors();
+/// assert_eq!(errors, vec!["error"]);
+/// ```
+pub trait ExtractErrors<E> {
+    /// Extract only the error values, discarding successes
+    fn extract_errors(self) -> Vec<E>;
+}
+
+/// Trait to split pipeline results into successful values and errors
+/// 
+/// # Examples
+/// 
+/// ```rust
+/// use pipex::PartitionResults;
+/// 
+/// let results: Vec<Result<i32, &str>> = vec![Ok(1), Err("error"), Ok(3)];
+/// assert_eq!(results.clone().partition_results(), (vec![1, 3], vec!["error"]));
+/// assert_eq!(results.ok_or_errors(), Err(vec!["error"]));
+/// 
+/// let results: Vec<Result<i32, &str>> = vec![Ok(1), Ok(3)];
+/// assert_eq!(results.ok_or_errors(), Ok(vec![1, 3]));
+/// ```
+pub trait PartitionResults<T, E> {
+    /// Split into `(successes, errors)`, each in their original order
+    fn partition_results(self) -> (Vec<T>, Vec<E>);
+
+    /// `Ok` with all successful values if there were no errors, otherwise `Err` with every error
+    fn ok_or_errors(self) -> Result<Vec<T>, Vec<E>>;
+}
+
 /// Trait to convert various result types into standard Result
 /// 
 /// This trait provides a uniform interface for converting different
@@ -66,6 +112,35 @@ impl<T, E> ExtractSuccessful<T> for Vec<Result<T, E>> {
     }
 }
 
+impl<T, E> ExtractErrors<E> for Vec<Result<T, E>> {
+    fn extract_errors(self) -> Vec<E> {
+        self.into_iter().filter_map(|r| r.err()).collect()
+    }
+}
+
+impl<T, E> PartitionResults<T, E> for Vec<Result<T, E>> {
+    fn partition_results(self) -> (Vec<T>, Vec<E>) {
+        let mut successes = Vec::new();
+        let mut errors = Vec::new();
+        for result in self {
+            match result {
+                Ok(value) => successes.push(value),
+                Err(error) => errors.push(error),
+            }
+        }
+        (successes, errors)
+    }
+
+    fn ok_or_errors(self) -> Result<Vec<T>, Vec<E>> {
+        let (successes, errors) = self.partition_results();
+        if errors.is_empty() {
+            Ok(successes)
+        } else {
+            Err(errors)
+        }
+    }
+}
+
 // Implementations for Result<T, E>
 impl<T, E> IntoResult<T, E> for Result<T, E> {
     fn into_result(self) -> Result<T, E> {
@@ -95,12 +170,16 @@ impl<T, E> CreateError<E> for PipexResult<T, E> {
 // PipelineResultHandler implementation for Vec<PipexResult<T, E>>
 impl<T, E> PipelineResultHandler<T, E> for Vec<PipexResult<T, E>> 
 where
-    T: 'static + Clone,
-    E: std::fmt::Debug + 'static + Clone,
+    T: 'static,
+    E: std::fmt::Debug + 'static,
 {
     fn handle_pipeline_results(self) -> Vec<Result<T, E>> {
         if let Some(first) = self.first() {
-            let strategy_name = first.strategy_name;
+            // Errors forwarded from earlier steps carry no strategy of their own
+            let strategy_name = self.iter()
+                .map(|pipex_result| pipex_result.strategy_name)
+                .find(|name| *name != "preserve_error")
+                .unwrap_or(first.strategy_name);
             let inner_results: Vec<Result<T, E>> = self
                 .into_iter()
                 .map(|pipex_result| pipex_result.result)
@@ -134,34 +213,876 @@ where
 #[doc(hidden)]
 pub trait IntoPipelineItem {
     type OutputValue;
+    type Error;
     type PipelineItem;
 
     fn into_pipeline_item(self) -> Self::PipelineItem;
 }
 
 #[doc(hidden)]
-impl<T: 'static, E: std::fmt::Debug> IntoPipelineItem for Result<T, E> {
+impl<T, E> IntoPipelineItem for Result<T, E> {
     type OutputValue = T;
-    type PipelineItem = Result<T, String>;
+    type Error = E;
+    type PipelineItem = Result<T, E>;
 
-    fn into_pipeline_item(self) -> Result<T, String> {
-        self.map_err(|e| format!("{:?}", e))
+    fn into_pipeline_item(self) -> Result<T, E> {
+        self
     }
 }
 
 #[doc(hidden)]
-impl<T: 'static, E: std::fmt::Debug> IntoPipelineItem for PipexResult<T, E> {
+impl<T, E> IntoPipelineItem for PipexResult<T, E> {
     type OutputValue = T;
-    type PipelineItem = PipexResult<T, String>;
+    type Error = E;
+    type PipelineItem = PipexResult<T, E>;
+
+    fn into_pipeline_item(self) -> PipexResult<T, E> {
+        self
+    }
+}
+
+#[doc(hidden)]
+pub trait IntoExpandedItem<E> {
+    type PipelineItem;
+
+    fn into_expanded_item(self) -> Self::PipelineItem;
+}
+
+#[doc(hidden)]
+impl<T, E> IntoExpandedItem<E> for Vec<T> {
+    type PipelineItem = Result<Vec<T>, E>;
+
+    fn into_expanded_item(self) -> Result<Vec<T>, E> {
+        Ok(self)
+    }
+}
+
+#[doc(hidden)]
+impl<I: IntoIterator, E> IntoExpandedItem<E> for Result<I, E> {
+    type PipelineItem = Result<Vec<I::Item>, E>;
+
+    fn into_expanded_item(self) -> Result<Vec<I::Item>, E> {
+        self.map(|items| items.into_iter().collect())
+    }
+}
+
+#[doc(hidden)]
+impl<I: IntoIterator, E> IntoExpandedItem<E> for PipexResult<I, E> {
+    type PipelineItem = PipexResult<Vec<I::Item>, E>;
+
+    fn into_expanded_item(self) -> PipexResult<Vec<I::Item>, E> {
+        PipexResult {
+            result: self.result.map(|items| items.into_iter().collect()),
+            strategy_name: self.strategy_name,
+        }
+    }
+}
+
+#[doc(hidden)]
+pub trait IntoScatteredItems<K> {
+    type PipelineItem;
+
+    fn into_scattered_items(self, keys: Vec<K>) -> Vec<Self::PipelineItem>;
+}
+
+#[doc(hidden)]
+impl<K, R> IntoScatteredItems<K> for Vec<R>
+where
+    R: IntoPipelineItem,
+    R::PipelineItem: CreateError<R::Error>,
+    R::Error: From<BatchError>,
+{
+    type PipelineItem = R::PipelineItem;
+
+    fn into_scattered_items(self, keys: Vec<K>) -> Vec<R::PipelineItem> {
+        if self.len() != keys.len() {
+            let error = BatchError::WrongLength { results: self.len(), inputs: keys.len() };
+            return keys.iter()
+                .map(|_| R::PipelineItem::create_error(error.clone().into()))
+                .collect();
+        }
+        self.into_iter().map(|r| r.into_pipeline_item()).collect()
+    }
+}
+
+#[doc(hidden)]
+impl<K, R, S> IntoScatteredItems<K> for std::collections::HashMap<K, R, S>
+where
+    K: std::hash::Hash + Eq + std::fmt::Debug,
+    R: IntoPipelineItem + Clone,
+    R::PipelineItem: CreateError<R::Error>,
+    R::Error: From<BatchError>,
+    S: std::hash::BuildHasher,
+{
+    type PipelineItem = R::PipelineItem;
+
+    // Items sharing a key each get a copy of its result
+    fn into_scattered_items(self, keys: Vec<K>) -> Vec<R::PipelineItem> {
+        keys.into_iter()
+            .map(|key| match self.get(&key) {
+                Some(r) => r.clone().into_pipeline_item(),
+                None => R::PipelineItem::create_error(BatchError::MissingResult(format!("{:?}", key)).into()),
+            })
+            .collect()
+    }
+}
+
+/// The keys of a keyed batch without duplicates, in order of first appearance
+#[doc(hidden)]
+pub fn unique_keys<K: std::hash::Hash + Eq + Clone>(keys: Vec<K>) -> Vec<K> {
+    let mut seen = std::collections::HashSet::with_capacity(keys.len());
+    keys.into_iter().filter(|key| seen.insert(key.clone())).collect()
+}
+
+#[doc(hidden)]
+pub trait TagIndex {
+    type Tagged;
+
+    fn tag_index(self, index: usize) -> Self::Tagged;
+}
+
+#[doc(hidden)]
+impl<T, E> TagIndex for Result<T, E> {
+    type Tagged = Result<(usize, T), E>;
+
+    fn tag_index(self, index: usize) -> Result<(usize, T), E> {
+        self.map(|value| (index, value))
+    }
+}
+
+#[doc(hidden)]
+impl<T, E> TagIndex for PipexResult<T, E> {
+    type Tagged = PipexResult<(usize, T), E>;
+
+    fn tag_index(self, index: usize) -> PipexResult<(usize, T), E> {
+        PipexResult {
+            result: self.result.map(|value| (index, value)),
+            strategy_name: self.strategy_name,
+        }
+    }
+}
+
+#[doc(hidden)]
+pub trait PairWithInput<P> {
+    type Paired;
+
+    fn pair_with_input(self, input: P) -> Self::Paired;
+}
+
+#[doc(hidden)]
+impl<P, T, E> PairWithInput<P> for Result<T, E> {
+    type Paired = Result<(P, T), E>;
+
+    fn pair_with_input(self, input: P) -> Result<(P, T), E> {
+        self.map(|value| (input, value))
+    }
+}
 
-    fn into_pipeline_item(self) -> PipexResult<T, String> {
+#[doc(hidden)]
+impl<P, T, E> PairWithInput<P> for PipexResult<T, E> {
+    type Paired = PipexResult<(P, T), E>;
+
+    fn pair_with_input(self, input: P) -> PipexResult<(P, T), E> {
         PipexResult {
-            result: self.result.map_err(|e| format!("{:?}", e)), 
+            result: self.result.map(|value| (input, value)),
             strategy_name: self.strategy_name,
         }
     }
 }
 
+#[doc(hidden)]
+pub trait RetryOutcome<E> {
+    fn retry_error(&self) -> Option<&E>;
+
+    fn into_exhausted(self, attempts: usize) -> Self;
+}
+
+#[doc(hidden)]
+impl<T, E> RetryOutcome<E> for Result<T, E>
+where
+    E: From<crate::retry::RetryError<E>>,
+{
+    fn retry_error(&self) -> Option<&E> {
+        self.as_ref().err()
+    }
+
+    fn into_exhausted(self, attempts: usize) -> Self {
+        self.map_err(|error| crate::retry::RetryError { attempts, error }.into())
+    }
+}
+
+#[doc(hidden)]
+impl<T, E> RetryOutcome<E> for PipexResult<T, E>
+where
+    E: From<crate::retry::RetryError<E>>,
+{
+    fn retry_error(&self) -> Option<&E> {
+        self.result.as_ref().err()
+    }
+
+    fn into_exhausted(self, attempts: usize) -> Self {
+        PipexResult {
+            result: self.result.into_exhausted(attempts),
+            strategy_name: self.strategy_name,
+        }
+    }
+}
+
+/// Tag of an item in a plain pipeline, which records nothing
+#[doc(hidden)]
+#[derive(Debug, Clone, Copy)]
+pub struct Untraced;
+
+/// Mode of a `traced` pipeline: every item runs to the end
+#[doc(hidden)]
+#[derive(Debug, Clone, Copy)]
+pub struct RunToEnd;
+
+/// Mode of a `fail_fast` pipeline: the first error stops the pipeline
+#[doc(hidden)]
+#[derive(Debug, Clone, Copy)]
+pub struct FailFast;
+
+/// Where an item of a `traced` or `fail_fast` pipeline came from
+#[doc(hidden)]
+#[derive(Debug, Clone)]
+pub struct Trace<M = RunToEnd> {
+    pub input_index: usize,
+    pub stage: usize,
+    pub stage_name: Option<&'static str>,
+    /// Traces of the items of a batch, given back to them by `unbatch`
+    pub batch: Option<Arc<[Trace<M>]>>,
+    pub mode: PhantomData<M>,
+}
+
+impl<M> Trace<M> {
+    pub fn new(input_index: usize) -> Self {
+        Self::at(input_index, 0, None)
+    }
+
+    fn at(input_index: usize, stage: usize, batch: Option<Arc<[Trace<M>]>>) -> Self {
+        Trace { input_index, stage, stage_name: None, batch, mode: PhantomData }
+    }
+
+    fn error<E>(&self, cause: E) -> PipelineError<E> {
+        PipelineError {
+            stage: self.stage,
+            stage_name: self.stage_name,
+            input_index: self.input_index,
+            cause,
+        }
+    }
+}
+
+/// An item of a `traced` or `fail_fast` pipeline
+#[doc(hidden)]
+#[derive(Debug)]
+pub struct Traced<R, M = RunToEnd> {
+    pub trace: Trace<M>,
+    pub item: R,
+}
+
+/// Tag of an item in an `indexed` pipeline: its position in the input
+#[doc(hidden)]
+#[derive(Debug, Clone)]
+pub struct InputIndex {
+    pub input_index: usize,
+    /// Tags of the items of a batch, given back to them by `unbatch`
+    pub batch: Option<Arc<[InputIndex]>>,
+}
+
+impl InputIndex {
+    pub fn new(input_index: usize) -> Self {
+        InputIndex { input_index, batch: None }
+    }
+}
+
+/// An item of an `indexed` pipeline
+#[doc(hidden)]
+#[derive(Debug)]
+pub struct Indexed<R> {
+    pub index: InputIndex,
+    pub item: R,
+}
+
+#[doc(hidden)]
+pub trait SplitItem {
+    type Tag: ItemTag;
+    type Result;
+    type Finished;
+
+    fn split_item(self) -> (Self::Tag, Self::Result);
+
+    fn finish_item(self) -> Self::Finished;
+
+    fn name_stage(self, name: &'static str) -> Self;
+}
+
+#[doc(hidden)]
+impl<T, E> SplitItem for Result<T, E> {
+    type Tag = Untraced;
+    type Result = Result<T, E>;
+    type Finished = Result<T, E>;
+
+    fn split_item(self) -> (Untraced, Result<T, E>) {
+        (Untraced, self)
+    }
+
+    fn finish_item(self) -> Result<T, E> {
+        self
+    }
+
+    fn name_stage(self, _name: &'static str) -> Self {
+        self
+    }
+}
+
+#[doc(hidden)]
+impl<R, M: Copy> SplitItem for Traced<R, M> {
+    type Tag = Trace<M>;
+    type Result = R;
+    type Finished = R;
+
+    fn split_item(self) -> (Trace<M>, R) {
+        (self.trace, self.item)
+    }
+
+    fn finish_item(self) -> R {
+        self.item
+    }
+
+    fn name_stage(mut self, name: &'static str) -> Self {
+        self.trace.stage_name = Some(name);
+        self
+    }
+}
+
+#[doc(hidden)]
+impl<R> SplitItem for Indexed<R> {
+    type Tag = InputIndex;
+    type Result = R;
+    type Finished = (usize, R);
+
+    fn split_item(self) -> (InputIndex, R) {
+        (self.index, self.item)
+    }
+
+    fn finish_item(self) -> (usize, R) {
+        (self.index.input_index, self.item)
+    }
+
+    fn name_stage(self, _name: &'static str) -> Self {
+        self
+    }
+}
+
+#[doc(hidden)]
+pub trait ItemTag: Clone + Sized {
+    type Item<R>;
+
+    /// Put an item back together after a step, without touching its errors
+    fn retag<R>(self, item: R) -> Self::Item<R>;
+
+    /// Tag of a batch, keeping the tags of its items; `tags` is not empty
+    fn batch_tag(tags: Vec<Self>) -> Self;
+
+    /// Tags for the `len` items a flattened item turns into
+    ///
+    /// A batch that kept its length gives every item its own tag back, anything else
+    /// passes its tag on to all of its items.
+    fn unbatch_tags(self, len: usize) -> Vec<Self>;
+
+    /// Position of the item in the pipeline's input, if the pipeline records it
+    fn input_index(&self) -> Option<usize>;
+}
+
+#[doc(hidden)]
+impl ItemTag for Untraced {
+    type Item<R> = R;
+
+    fn retag<R>(self, item: R) -> R {
+        item
+    }
+
+    fn batch_tag(_tags: Vec<Self>) -> Self {
+        Untraced
+    }
+
+    fn unbatch_tags(self, len: usize) -> Vec<Self> {
+        vec![Untraced; len]
+    }
+
+    fn input_index(&self) -> Option<usize> {
+        None
+    }
+}
+
+#[doc(hidden)]
+impl<M: Copy> ItemTag for Trace<M> {
+    type Item<R> = Traced<R, M>;
+
+    fn retag<R>(self, item: R) -> Traced<R, M> {
+        Traced { trace: Trace::at(self.input_index, self.stage + 1, self.batch), item }
+    }
+
+    fn batch_tag(tags: Vec<Self>) -> Self {
+        let first = &tags[0];
+        Trace {
+            input_index: first.input_index,
+            stage: first.stage,
+            stage_name: first.stage_name,
+            batch: Some(tags.into()),
+            mode: PhantomData,
+        }
+    }
+
+    fn unbatch_tags(self, len: usize) -> Vec<Self> {
+        match &self.batch {
+            Some(batch) if batch.len() == len => batch
+                .iter()
+                .map(|trace| Trace { stage: self.stage, stage_name: self.stage_name, ..trace.clone() })
+                .collect(),
+            _ => vec![Trace { batch: None, ..self }; len],
+        }
+    }
+
+    fn input_index(&self) -> Option<usize> {
+        Some(self.input_index)
+    }
+}
+
+#[doc(hidden)]
+impl ItemTag for InputIndex {
+    type Item<R> = Indexed<R>;
+
+    fn retag<R>(self, item: R) -> Indexed<R> {
+        Indexed { index: self, item }
+    }
+
+    fn batch_tag(tags: Vec<Self>) -> Self {
+        InputIndex { input_index: tags[0].input_index, batch: Some(tags.into()) }
+    }
+
+    fn unbatch_tags(self, len: usize) -> Vec<Self> {
+        match self.batch {
+            Some(batch) if batch.len() == len => batch.to_vec(),
+            _ => vec![InputIndex::new(self.input_index); len],
+        }
+    }
+
+    fn input_index(&self) -> Option<usize> {
+        Some(self.input_index)
+    }
+}
+
+#[doc(hidden)]
+pub trait JoinOutput<R> {
+    type Item;
+
+    /// Put an item back together from a step's output, recording where its error happened
+    fn join_output(self, output: R) -> Self::Item;
+}
+
+#[doc(hidden)]
+impl<R> JoinOutput<R> for Untraced {
+    type Item = R;
+
+    fn join_output(self, output: R) -> R {
+        output
+    }
+}
+
+#[doc(hidden)]
+impl<R> JoinOutput<R> for InputIndex {
+    type Item = Indexed<R>;
+
+    fn join_output(self, output: R) -> Indexed<R> {
+        self.retag(output)
+    }
+}
+
+#[doc(hidden)]
+impl<T, E, M: Copy> JoinOutput<Result<T, E>> for Trace<M> {
+    type Item = Traced<Result<T, PipelineError<E>>, M>;
+
+    fn join_output(self, output: Result<T, E>) -> Self::Item {
+        let output = output.map_err(|cause| self.error(cause));
+        self.retag(output)
+    }
+}
+
+#[doc(hidden)]
+impl<T, E, M: Copy> JoinOutput<PipexResult<T, E>> for Trace<M> {
+    type Item = Traced<PipexResult<T, PipelineError<E>>, M>;
+
+    fn join_output(self, output: PipexResult<T, E>) -> Self::Item {
+        let output = PipexResult {
+            result: output.result.map_err(|cause| self.error(cause)),
+            strategy_name: output.strategy_name,
+        };
+        self.retag(output)
+    }
+}
+
+#[doc(hidden)]
+pub trait SplitError<E> {
+    type Cause;
+    type Context: WrapCause;
+
+    fn split_error(&self, error: E) -> (Self::Cause, Self::Context);
+}
+
+#[doc(hidden)]
+impl<E> SplitError<E> for Untraced {
+    type Cause = E;
+    type Context = Untraced;
+
+    fn split_error(&self, error: E) -> (E, Untraced) {
+        (error, Untraced)
+    }
+}
+
+#[doc(hidden)]
+impl<E> SplitError<E> for InputIndex {
+    type Cause = E;
+    type Context = Untraced;
+
+    fn split_error(&self, error: E) -> (E, Untraced) {
+        (error, Untraced)
+    }
+}
+
+#[doc(hidden)]
+impl<E, M> SplitError<PipelineError<E>> for Trace<M> {
+    type Cause = E;
+    type Context = PipelineError<()>;
+
+    fn split_error(&self, error: PipelineError<E>) -> (E, PipelineError<()>) {
+        let PipelineError { stage, stage_name, input_index, cause } = error;
+        (cause, PipelineError { stage, stage_name, input_index, cause: () })
+    }
+}
+
+/// Tags whose errors `recover` may turn back into values
+///
+/// A `fail_fast` pipeline only has an error left once it has stopped, so it has no impl.
+#[doc(hidden)]
+#[diagnostic::on_unimplemented(
+    message = "`recover` cannot be used in a `fail_fast` pipeline",
+    note = "the first error stops a `fail_fast` pipeline, handle it on the `Result` the pipeline returns"
+)]
+pub trait Recover {}
+
+#[doc(hidden)]
+impl Recover for Untraced {}
+
+#[doc(hidden)]
+impl Recover for InputIndex {}
+
+#[doc(hidden)]
+impl Recover for Trace<RunToEnd> {}
+
+/// Checks at compile time that a `recover` step may handle errors tagged with `tag`
+#[doc(hidden)]
+pub fn recoverable<T: Recover>(tag: T) -> T {
+    tag
+}
+
+#[doc(hidden)]
+pub trait WrapCause {
+    type Wrapped<C>;
+
+    fn wrap_cause<C>(self, cause: C) -> Self::Wrapped<C>;
+}
+
+#[doc(hidden)]
+impl WrapCause for Untraced {
+    type Wrapped<C> = C;
+
+    fn wrap_cause<C>(self, cause: C) -> C {
+        cause
+    }
+}
+
+#[doc(hidden)]
+impl WrapCause for PipelineError<()> {
+    type Wrapped<C> = PipelineError<C>;
+
+    fn wrap_cause<C>(self, cause: C) -> PipelineError<C> {
+        self.map_cause(|()| cause)
+    }
+}
+
+#[doc(hidden)]
+pub trait FlattenItem {
+    type Item;
+
+    fn flatten_item(self) -> Vec<Self::Item>;
+}
+
+#[doc(hidden)]
+impl<T, E> FlattenItem for Result<Vec<T>, E> {
+    type Item = Result<T, E>;
+
+    fn flatten_item(self) -> Vec<Result<T, E>> {
+        match self {
+            Ok(items) => items.into_iter().map(Ok).collect(),
+            Err(e) => vec![Err(e)],
+        }
+    }
+}
+
+#[doc(hidden)]
+impl<T, E, M: Copy> FlattenItem for Traced<Result<Vec<T>, E>, M> {
+    type Item = Traced<Result<T, E>, M>;
+
+    fn flatten_item(self) -> Vec<Traced<Result<T, E>, M>> {
+        let items = self.item.flatten_item();
+        self.trace
+            .unbatch_tags(items.len())
+            .into_iter()
+            .zip(items)
+            .map(|(trace, item)| Traced { trace, item })
+            .collect()
+    }
+}
+
+#[doc(hidden)]
+impl<T, E> FlattenItem for Indexed<Result<Vec<T>, E>> {
+    type Item = Indexed<Result<T, E>>;
+
+    fn flatten_item(self) -> Vec<Indexed<Result<T, E>>> {
+        let items = self.item.flatten_item();
+        self.index
+            .unbatch_tags(items.len())
+            .into_iter()
+            .zip(items)
+            .map(|(index, item)| Indexed { index, item })
+            .collect()
+    }
+}
+
+#[doc(hidden)]
+pub trait ApplyStrategies {
+    type Items;
+
+    fn apply_strategies(self) -> Self::Items;
+}
+
+#[doc(hidden)]
+impl<T, E> ApplyStrategies for Vec<Result<T, E>> {
+    type Items = Vec<Result<T, E>>;
+
+    fn apply_strategies(self) -> Self::Items {
+        self
+    }
+}
+
+#[doc(hidden)]
+impl<T, E> ApplyStrategies for Vec<PipexResult<T, E>>
+where
+    T: 'static,
+    E: std::fmt::Debug + 'static,
+{
+    type Items = Vec<Result<T, E>>;
+
+    fn apply_strategies(self) -> Self::Items {
+        self.handle_pipeline_results()
+    }
+}
+
+#[doc(hidden)]
+impl<T, E> ApplyStrategies for Vec<Traced<Result<T, E>>> {
+    type Items = Vec<Traced<Result<T, E>>>;
+
+    fn apply_strategies(self) -> Self::Items {
+        self
+    }
+}
+
+#[doc(hidden)]
+impl<T, E> ApplyStrategies for Vec<Traced<PipexResult<T, PipelineError<E>>>>
+where
+    T: 'static,
+    E: std::fmt::Debug + 'static,
+{
+    type Items = Vec<Traced<Result<T, PipelineError<E>>>>;
+
+    fn apply_strategies(self) -> Self::Items {
+        let Some(stage) = self.first().map(|traced| traced.trace.stage) else {
+            return Vec::new();
+        };
+
+        // Strategies see `(input_index, value)` pairs, so the items they keep can be traced again
+        let mut batches = StrategyBatches::default();
+        let indexed_results = self
+            .into_iter()
+            .map(|traced| {
+                let input_index = traced.trace.input_index;
+                if traced.item.result.is_ok() {
+                    batches.insert(input_index, traced.trace.batch);
+                }
+                PipexResult {
+                    result: traced.item.result.map(|value| (input_index, value)),
+                    strategy_name: traced.item.strategy_name,
+                }
+            })
+            .collect::<Vec<_>>();
+
+        indexed_results
+            .handle_pipeline_results()
+            .into_iter()
+            .map(|result| match result {
+                Ok((input_index, value)) => Traced {
+                    trace: Trace::at(input_index, stage, batches.take(input_index)),
+                    item: Ok(value),
+                },
+                Err(error) => Traced { trace: Trace::at(error.input_index, stage, None), item: Err(error) },
+            })
+            .collect()
+    }
+}
+
+#[doc(hidden)]
+impl<T, E> ApplyStrategies for Vec<Traced<Result<T, E>, FailFast>> {
+    type Items = Vec<Traced<Result<T, E>, FailFast>>;
+
+    fn apply_strategies(mut self) -> Self::Items {
+        // Only the first error goes on, so later steps have nothing left to run
+        match self.iter().position(|traced| traced.item.is_err()) {
+            Some(position) => vec![self.swap_remove(position)],
+            None => self,
+        }
+    }
+}
+
+#[doc(hidden)]
+impl<T, E> ApplyStrategies for Vec<Traced<PipexResult<T, E>, FailFast>> {
+    type Items = Vec<Traced<Result<T, E>, FailFast>>;
+
+    fn apply_strategies(self) -> Self::Items {
+        // Error strategies do not apply, every error stops a fail-fast pipeline
+        self.into_iter()
+            .map(|traced| Traced { trace: traced.trace, item: traced.item.result })
+            .collect::<Vec<_>>()
+            .apply_strategies()
+    }
+}
+
+#[doc(hidden)]
+impl<T, E> ApplyStrategies for Vec<Indexed<Result<T, E>>> {
+    type Items = Vec<Indexed<Result<T, E>>>;
+
+    fn apply_strategies(self) -> Self::Items {
+        self
+    }
+}
+
+#[doc(hidden)]
+impl<T, E> ApplyStrategies for Vec<Indexed<PipexResult<T, E>>>
+where
+    T: 'static,
+    E: std::fmt::Debug + 'static,
+{
+    type Items = Vec<Indexed<Result<T, E>>>;
+
+    fn apply_strategies(self) -> Self::Items {
+        // Strategies see `(input_index, value)` and `(input_index, error)` pairs
+        let mut batches = StrategyBatches::default();
+        let indexed_results = self
+            .into_iter()
+            .map(|indexed| {
+                let input_index = indexed.index.input_index;
+                if indexed.item.result.is_ok() {
+                    batches.insert(input_index, indexed.index.batch);
+                }
+                PipexResult {
+                    result: indexed.item.result
+                        .map(|value| (input_index, value))
+                        .map_err(|error| (input_index, error)),
+                    strategy_name: indexed.item.strategy_name,
+                }
+            })
+            .collect::<Vec<_>>();
+
+        indexed_results
+            .handle_pipeline_results()
+            .into_iter()
+            .map(|result| match result {
+                Ok((input_index, value)) => Indexed {
+                    index: InputIndex { input_index, batch: batches.take(input_index) },
+                    item: Ok(value),
+                },
+                Err((input_index, error)) => Indexed { index: InputIndex::new(input_index), item: Err(error) },
+            })
+            .collect()
+    }
+}
+
+/// Batches of the values handed to a strategy, taken back by input index for the values it keeps
+struct StrategyBatches<Tag> {
+    by_input_index: HashMap<usize, VecDeque<Option<Arc<[Tag]>>>>,
+}
+
+impl<Tag> Default for StrategyBatches<Tag> {
+    fn default() -> Self {
+        StrategyBatches { by_input_index: HashMap::new() }
+    }
+}
+
+impl<Tag> StrategyBatches<Tag> {
+    fn insert(&mut self, input_index: usize, batch: Option<Arc<[Tag]>>) {
+        self.by_input_index.entry(input_index).or_default().push_back(batch);
+    }
+
+    fn take(&mut self, input_index: usize) -> Option<Arc<[Tag]>> {
+        self.by_input_index.get_mut(&input_index)?.pop_front().flatten()
+    }
+}
+
+#[doc(hidden)]
+pub trait FinishItems {
+    type Output;
+
+    fn finish_items(self) -> Self::Output;
+}
+
+#[doc(hidden)]
+impl<T, E> FinishItems for Vec<Result<T, E>> {
+    type Output = Vec<Result<T, E>>;
+
+    fn finish_items(self) -> Self::Output {
+        self
+    }
+}
+
+#[doc(hidden)]
+impl<R> FinishItems for Vec<Traced<R>> {
+    type Output = Vec<R>;
+
+    fn finish_items(self) -> Self::Output {
+        self.into_iter().map(|traced| traced.item).collect()
+    }
+}
+
+#[doc(hidden)]
+impl<T, E> FinishItems for Vec<Traced<Result<T, E>, FailFast>> {
+    type Output = Result<Vec<T>, E>;
+
+    fn finish_items(self) -> Self::Output {
+        self.into_iter().map(|traced| traced.item).collect()
+    }
+}
+
+#[doc(hidden)]
+impl<R> FinishItems for Vec<Indexed<R>> {
+    type Output = Vec<(usize, R)>;
+
+    fn finish_items(self) -> Self::Output {
+        self.into_iter().map(|indexed| (indexed.index.input_index, indexed.item)).collect()
+    }
+}
+
 /// Marker trait for pure functions.
 /// 
 /// This trait is automatically implemented by the `#[pure]` macro for functions